@@ -0,0 +1,37 @@
+// Expression statements whose results are never used are only compiled if they
+// could have side effects, such as calling a journey or overflowing.
+#include <squire/program.h>
+#include <squire/journey.h>
+
+#include <stdio.h>
+
+static unsigned main_codelen(const char *stream) {
+	struct sq_program program;
+
+	sq_program_compile(&program, stream);
+	unsigned codelen = program.main->patterns[0].code.codelen;
+	sq_program_finish(&program);
+
+	return codelen;
+}
+
+int main(void) {
+	unsigned empty = main_codelen("journey f() { }");
+
+	if (main_codelen("journey f() { } III + IV;") != empty) {
+		fputs("`III + IV;` should compile to nothing\n", stderr);
+		return 1;
+	}
+
+	if (main_codelen("journey f() { } f();") == empty) {
+		fputs("`f();` should still call `f`\n", stderr);
+		return 1;
+	}
+
+	if (main_codelen("journey f() { } I + 0x7FF_FFFF_FFFF_FFFF;") == empty) {
+		fputs("overflowing arithmetic should still be compiled, so that it catapults\n", stderr);
+		return 1;
+	}
+
+	return 0;
+}
@@ -0,0 +1,32 @@
+// `inquire` reads lines from a program's `input`, which can be changed from
+// `stdin` to feed it canned input. Once the input runs out, it returns `ni`.
+#include <squire/program.h>
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+	struct sq_program program;
+	char input[] = "Gawain\nLancelot\r\n", *buffer;
+	size_t length;
+
+	sq_program_compile(&program, "a = inquire()\nb = inquire()\nc = inquire()\nproclaim(\"{a}|{b}|{c}\")");
+	program.input = fmemopen(input, strlen(input), "r");
+	program.output = open_memstream(&buffer, &length);
+	sq_program_run(&program, 0, NULL);
+	fclose(program.input);
+	fclose(program.output);
+	sq_program_finish(&program);
+
+	const char *expected = "Gawain|Lancelot|ni\n";
+
+	if (strcmp(buffer, expected)) {
+		fprintf(stderr, "expected the output to be:\n%s\nbut it was:\n%s\n", expected, buffer);
+		free(buffer);
+		return 1;
+	}
+
+	free(buffer);
+	return 0;
+}
@@ -0,0 +1,36 @@
+// Untrusted programs can be given a `max_steps`, so that they can't run forever.
+#include <squire/program.h>
+#include <squire/exception.h>
+#include <squire/text.h>
+
+#include <stdio.h>
+#include <string.h>
+
+int main(void) {
+	struct sq_program program;
+
+	sq_program_compile(&program, "whilst yea { }");
+	program.max_steps = 1000;
+
+	if (setjmp(exception_handlers[current_exception_handler++])) {
+		if (!sq_value_is_text(exception)
+			|| strcmp(sq_value_as_text(exception)->ptr, "instruction limit of 1000 exceeded")
+		) {
+			fputs("the loop should have been stopped by the instruction limit\n", stderr);
+			return 1;
+		}
+
+		if (program.steps != program.max_steps + 1) {
+			fprintf(stderr, "the loop should have run exactly 1000 steps, not %lu\n", program.steps - 1);
+			return 1;
+		}
+
+		sq_program_reset(&program);
+		sq_program_finish(&program);
+		return 0;
+	}
+
+	sq_program_run(&program, 0, NULL);
+	fputs("`whilst yea { }` should never finish\n", stderr);
+	return 1;
+}
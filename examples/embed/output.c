@@ -0,0 +1,30 @@
+// `proclaim`, `proclaimn`, and `dump` write to a program's `output`, which can be
+// changed from `stdout` to capture what the program prints.
+#include <squire/program.h>
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+	struct sq_program program;
+	char *buffer;
+	size_t length;
+
+	sq_program_compile(&program, "proclaimn('hail, ')\nproclaim(𝔖𝔦𝔯 𝔊𝔞𝔴𝔞𝔦𝔫)\ndump([I])");
+	program.output = open_memstream(&buffer, &length);
+	sq_program_run(&program, 0, NULL);
+	fclose(program.output);
+	sq_program_finish(&program);
+
+	const char *expected = "hail, Sir Gawain\nBook(Numeral(1))\n";
+
+	if (strcmp(buffer, expected)) {
+		fprintf(stderr, "expected the output to be:\n%s\nbut it was:\n%s\n", expected, buffer);
+		free(buffer);
+		return 1;
+	}
+
+	free(buffer);
+	return 0;
+}
@@ -0,0 +1,40 @@
+// A REPL can use `sq_parse_partial_statements` to tell whether it needs to read
+// more lines before it has a whole statement.
+#include <squire/parse.h>
+#include <squire/exception.h>
+#include <squire/text.h>
+
+#include <stdio.h>
+#include <string.h>
+
+int main(void) {
+	struct statements *stmts;
+
+	if (sq_parse_partial_statements("if x {", &stmts) != SQ_PARSE_INCOMPLETE) {
+		fputs("`if x {` should be incomplete\n", stderr);
+		return 1;
+	}
+
+	if (sq_parse_partial_statements("if x {\n\tproclaim(x)\n}", &stmts) != SQ_PARSE_COMPLETE
+		|| stmts->len != 1
+	) {
+		fputs("`if x { proclaim(x) }` should be a single complete statement\n", stderr);
+		return 1;
+	}
+
+	// anything after the statements that can't start another one is an error, not more input.
+	if (setjmp(exception_handlers[current_exception_handler++])) {
+		if (!sq_value_is_text(exception)
+			|| strcmp(sq_value_as_text(exception)->ptr, "line 1, column 7: unexpected token")
+		) {
+			fputs("`x = I )` should be an unexpected token\n", stderr);
+			return 1;
+		}
+
+		return 0;
+	}
+
+	sq_parse_partial_statements("x = I )", &stmts);
+	fputs("`x = I )` should catapult\n", stderr);
+	return 1;
+}
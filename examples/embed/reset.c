@@ -0,0 +1,61 @@
+// After an exception escapes `sq_program_run`, the journeys it was in the middle
+// of are left behind. `sq_program_reset` clears them away, so the same program
+// can be run again.
+#include <squire/program.h>
+#include <squire/journey.h>
+#include <squire/exception.h>
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static const char *source =
+	"journey descend(depth) {\n"
+	"	if depth == N { catapult 'the bottom' }\n"
+	"	reward I + descend(depth - I)\n"
+	"}\n"
+	"if length(ARGV) { descend(X) }\n"
+	"proclaim('made it')\n";
+
+int main(void) {
+	struct sq_program program;
+	unsigned depth;
+	char *buffer;
+	size_t length;
+
+	sq_program_compile(&program, source);
+	program.output = open_memstream(&buffer, &length);
+
+	if (!setjmp(exception_handlers[current_exception_handler++])) {
+		sq_program_run(&program, 1, (const char *[]) { "descend" });
+		fputs("the first run should catapult\n", stderr);
+		return 1;
+	}
+
+	sq_journey_stack(&depth);
+	if (depth == 0) {
+		fputs("the first run should have left its journeys behind\n", stderr);
+		return 1;
+	}
+
+	sq_program_reset(&program);
+
+	sq_journey_stack(&depth);
+	if (depth != 0) {
+		fprintf(stderr, "resetting should clear every journey, but %u are left\n", depth);
+		return 1;
+	}
+
+	sq_program_run(&program, 0, NULL);
+	fclose(program.output);
+	sq_program_finish(&program);
+
+	if (strcmp(buffer, "made it\n")) {
+		fprintf(stderr, "expected the second run to print 'made it', not:\n%s\n", buffer);
+		free(buffer);
+		return 1;
+	}
+
+	free(buffer);
+	return 0;
+}
@@ -104,8 +104,9 @@ typedef long ssize_t;
 
 /** Fix `index`, making it relative to `book`'s length.
  * 
- * If it's positive, it's simply returned. If it's negative, it starts from the
- * end. If it's still negative after that, `sq_throw` is called.
+ * Indices start at `I`, so positive indices are returned one less, and negative
+ * ones start from the end. If `index` is zero, or if it's negative and before
+ * the start of `book`, `sq_throw` is called.
  */
 size_t sq_book_fix_index(const struct sq_book *book, ssize_t index);
 
@@ -119,12 +120,16 @@ static inline sq_value sq_book_delete2(struct sq_book *book, ssize_t index) {
 	return sq_book_delete(book, sq_book_fix_index(book, index));
 }
 
-// Same as `sq_book_index`, but it accepts negative indices.
+// Same as `sq_book_index`, but it accepts negative indices. Just like positive
+// indices, negative ones that are out of bounds return `ni`.
 static inline sq_value sq_book_index2(const struct sq_book *book, ssize_t index) {
+	if (index < 0 && (ssize_t) book->length < -index)
+		return SQ_NI;
+
 	return sq_book_index(book, sq_book_fix_index(book, index));
 }
 
-// Same as `sq_book_index`, but it accepts negative indices.
+// Same as `sq_book_index_assign`, but it accepts negative indices.
 static inline void sq_book_index_assign2(struct sq_book *book, ssize_t index, sq_value value) {
 	sq_book_index_assign(book, sq_book_fix_index(book, index), value);
 }
@@ -141,4 +146,10 @@ struct sq_book *sq_book_map(const struct sq_book *book, const struct sq_journey
 struct sq_book *sq_book_select(const struct sq_book *book, const struct sq_journey *func);
 sq_value sq_book_reduce(const struct sq_book *book, const struct sq_journey *func);
 
+/** Finds the first page of `book` which `sq_value_eql`s `value`.
+ *
+ * Like indexing, the returned index starts at `I`. If no page matches, `0` is returned.
+ */
+size_t sq_book_index_of(const struct sq_book *book, sq_value value);
+
 #endif /* !SQ_BOOK_H */
@@ -9,22 +9,38 @@ enum sq_interrupt {
 	SQ_INT_TOBOOK       = 0x04, // [A,DST] DST <- A.to_book()
 	SQ_INT_TOCODEX      = 0x05, // [A,DST] DST <- A.to_codex()
 	SQ_INT_KINDOF       = 0x06, // [A,DST] DST <- A.genus
+	SQ_INT_KINDNAME     = 0x07, // [A,DST] DST <- name of A's genus, as a text
 
 	SQ_INT_PRINT        = 0x10, // [A,DST] Print `A`, DST <- ni
 	SQ_INT_PRINTLN      = 0x11, // [A,DST] Print `A` with a newline, DST <- ni
 	SQ_INT_DUMP         = 0x12, // [A,DST] Dumps out `A`, DST <- A
 	SQ_INT_PROMPT       = 0x13, // [DST] DST <- next line from stdin
 	SQ_INT_SYSTEM       = 0x14, // [CMD,DST] DST <- stdout of running `cmd`.
-	SQ_INT_EXIT         = 0x15, // [CODE] Exits with the given code.
+	SQ_INT_EXIT         = 0x15, // [CODE,DST] Exits with the given code; DST is never set.
 	SQ_INT_RANDOM       = 0x16, // [DST] DST <- random numeral
 
 	SQ_INT_SUBSTR       = 0x20, // [A,B,C,DST] DST <- A[B..B+C]
 	SQ_INT_LENGTH       = 0x21, // [A,DST] DST <- length A: book/codex/text
+	SQ_INT_UPPER        = 0x22, // [A,DST] DST <- A.to_text().upper()
+	SQ_INT_LOWER        = 0x23, // [A,DST] DST <- A.to_text().lower()
+	SQ_INT_TRIM         = 0x24, // [A,DST] DST <- A.to_text() without leading or trailing whitespace
+	SQ_INT_TRIM_START   = 0x25, // [A,DST] DST <- A.to_text() without leading whitespace
+	SQ_INT_TRIM_END     = 0x26, // [A,DST] DST <- A.to_text() without trailing whitespace
+	SQ_INT_SPLIT        = 0x27, // [A,B,DST] DST <- A.to_text() split on B.to_text(), or into characters if it's empty
+	SQ_INT_JOIN         = 0x28, // [A,B,DST] DST <- A's pages joined with B.to_text()
+	SQ_INT_STARTS_WITH  = 0x29, // [A,B,DST] DST <- whether A.to_text() starts with B.to_text()
+	SQ_INT_ENDS_WITH    = 0x2A, // [A,B,DST] DST <- whether A.to_text() ends with B.to_text()
+	SQ_INT_REPLACE      = 0x2B, // [A,B,C,DST] DST <- A.to_text() with each B.to_text() replaced by C.to_text()
 
 	SQ_INT_CODEX_NEW    = 0x30, // [N,...,DST] DST <- N key-value pairs.
 	SQ_INT_BOOK_NEW     = 0x31, // [N,...,DST] DST <- N-length array.
 	SQ_INT_ARRAY_INSERT = 0x32, // [A,B,C,DST] A.insert(len=B,pos=C); (Stores in DST, though this is not intended)
 	SQ_INT_ARRAY_DELETE = 0x33, // [A,B,DST] DST <- A.delete(B)
+	SQ_INT_RANGE_NEW    = 0x34, // [A,B,DST] DST <- A..B
+	SQ_INT_JOURNEY_CAPTURE = 0x35, // [J,N,...,DST] DST <- a closure of J capturing N values.
+	SQ_INT_ARRAY_CONTAINS = 0x36, // [A,B,DST] DST <- whether any page of A equals B (or, for a text, whether B.to_text() is in it)
+	SQ_INT_ARRAY_INDEX_OF = 0x37, // [A,B,DST] DST <- the index of the first page of A equal to B, or ni
+	SQ_INT_ARRAY_UNPACK = 0x38, // [A,N,R,DST] Throws unless A has N pages (or at least N if R); DST <- the rest
 
 	SQ_INT_ARABIC       = 0x40, // [A,DST] DST <- A.to_numeral().arabic()
 	SQ_INT_ROMAN        = 0x41, // [A,DST] DST <- A.to_numeral().roman()
@@ -54,13 +70,17 @@ enum sq_opcode {
 	SQ_OC_JMP_TRUE      = 0x22, // [CND,POS] IP <- POS if CND if true
 	SQ_OC_CALL          = 0x23, // [FN,NUM,...] Calls FN; NUM args are read
 	SQ_OC_RETURN        = 0x24, // [IDX] Returns the given value
-	SQ_OC_COMEFROM      = 0x25, // [AMNT,...] Performs COMEFROM for AMNT times
+	SQ_OC_COMEFROM      = 0x25, // [AMNT,END,...] Forks to each of AMNT `whence`s, or goes to END if none
 	SQ_OC_TRYCATCH      = 0x26, // [POS,ERR] Go when `catapult`s occur, set `ERR`
 	SQ_OC_THROW         = 0x27, // [IDX] Throws an exception
+	SQ_OC_KWCALL        = 0x28, // [FN,NUM,KWNUM,...] Like CALL, then KWNUM [CNST,IDX] keyword args are read
 	SQ_OC_POPTRYCATCH   = 0x29, // [] Removes a `catch` block from the stack.
 #ifndef SQ_NMOON_JOKE
 	SQ_OC_WERE_JMP      = 0x2A, // same as JMP_FALSE, but 1% chance not to on full moon
 #endif /* !SQ_MOON_JOKE */
+	SQ_OC_TAILCALL      = 0x2B, // [FN,NUM,...] Like CALL, but returns FN's result by reusing the current journey's frame
+	SQ_OC_ITER_START    = 0x2C, // [A,DST] DST <- position before A's first element; throws if A can't be iterated
+	SQ_OC_ITER_NEXT     = 0x2D, // [A,POS,DST,END] DST <- A's element at POS and advance POS, or IP <- END if none are left
 
 	SQ_OC_NOT           = 0x40, // [A,DST] DST <- !A
 	SQ_OC_NEG           = 0x41, // [A,DST] DST <- -A` (ie unary minus)
@@ -80,6 +100,11 @@ enum sq_opcode {
 	SQ_OC_INDEX         = 0x4F, // [A,B,DST] DST <- A[B]
 	SQ_OC_INDEX_ASSIGN  = 0x50, // [A,B,C] Performs `A[B]=C`; no destination.
 	SQ_OC_MATCHES       = 0x51, // [A,B,DST] DST <- A.matches(B)
+	SQ_OC_BAND          = 0x52, // [A,B,DST] DST <- A & B
+	SQ_OC_BOR           = 0x53, // [A,B,DST] DST <- A | B
+	SQ_OC_BXOR          = 0x54, // [A,B,DST] DST <- A ~ B
+	SQ_OC_SHL           = 0x55, // [A,B,DST] DST <- A << B
+	SQ_OC_SHR           = 0x56, // [A,B,DST] DST <- A >> B
 
 	SQ_OC_CLOAD         = 0x60, // [CNST,DST] DST <- constant `CNST`
 	SQ_OC_GLOAD         = 0x61, // [GLBL,DST] DST <- global `GLBL`
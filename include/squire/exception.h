@@ -15,6 +15,12 @@ extern sq_value exception;
 extern unsigned current_exception_handler;
 extern struct sq_form sq_exception_form;
 
+// The names of the journeys that were being run when `exception` was thrown, innermost first.
+extern struct sq_backtrace {
+	unsigned len, cap;
+	const char **names;
+} sq_exception_backtrace;
+
 struct sq_program;
 void sq_exception_init(struct sq_program *program);
 
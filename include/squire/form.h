@@ -102,13 +102,17 @@ void sq_form_dump(FILE *out, const struct sq_form *form);
 
 /** Fetches an essence (static field) on `form` named `name`.
  * 
- * If no such essence exists, `NULL` is returned.
+ * If `form` doesn't have one, its parents are searched depth-first, in the
+ * order they were declared; the first match is used. If no such essence
+ * exists, `NULL` is returned.
  */
 struct sq_essence *sq_form_lookup_essence(struct sq_form *form, const char *name);
 
 /** Fetches a recollection (class function) on `form` named `name`.
  * 
- * If no such recollection exists, `NULL` is returned.
+ * If `form` doesn't have one, its parents are searched depth-first, in the
+ * order they were declared; the first match is used. If no such
+ * recollection exists, `NULL` is returned.
  */
 struct sq_journey *sq_form_lookup_recollection(struct sq_form *form, const char *name);
 
@@ -145,13 +149,16 @@ sq_value *sq_imitation_lookup_matter(struct sq_imitation *imitation, const char
 
 /** Fetches a change (instance method) from `imitation` with the given `name`.
  * 
- * If no change with the given name exists, `NULL` is returned.
+ * Changes are searched for just like `sq_form_lookup_recollection`: first on
+ * `imitation`'s form, then on its parents depth-first, so a form's own changes
+ * override those of its parents. If no change with the given name exists,
+ * `NULL` is returned.
  */
 struct sq_journey *sq_imitation_lookup_change(struct sq_imitation *imitation, const char *name);
 
-/** Looks up either an `essence` or `recollection` with the given `name`.
+/** Looks up a change or matter on `imitation`, or an essence on its form, with the given `name`.
  * 
- * If neither exists, `SQ_UNDEFINED` is returned.
+ * If none exist, `SQ_UNDEFINED` is returned.
  * Note that unlike `sq_imitation_lookup_matter` and `sq_imitation_lookup_change`,
  * this function passes ownership of the returned `sq_value` to the caller.
  */
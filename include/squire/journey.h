@@ -15,6 +15,22 @@ struct sq_args {
 
 #define SQ_JOURNEY_MAX_ARGC 32 // seems like a reasonable maximum
 
+#ifndef SQ_JOURNEY_MAX_DEPTH
+# define SQ_JOURNEY_MAX_DEPTH 10000
+#endif /* !SQ_JOURNEY_MAX_DEPTH */
+
+// How deeply journey calls can be nested before an exception is thrown, so that
+// runaway recursion doesn't overflow the C stack. Defaults to `SQ_JOURNEY_MAX_DEPTH`.
+extern unsigned sq_journey_max_depth;
+
+// Gets the names of the journeys currently being run, innermost last, and stores
+// how many there are in `depth`.
+const char *const *sq_journey_stack(unsigned *depth);
+
+// Forgets every journey that was being run, and any pending tail call. This is
+// only needed when an exception escapes the interpreter entirely.
+void sq_journey_reset_stack(void);
+
 struct sq_codeblock {
 	unsigned nlocals, nconsts, codelen;
 	sq_value *consts;
@@ -26,21 +42,34 @@ struct sq_journey_argument {
 	int default_start, genus_start; // will be `-1` if no default or genus is supplied.
 };
 
+// Where a pattern keeps one of its journey's captured values: `captures[index]` is
+// copied into the local `local` before the pattern runs.
+struct sq_journey_capture {
+	unsigned index, local;
+};
+
 struct sq_journey_pattern {
-	unsigned pargc, kwargc, start_index;
+	unsigned pargc, kwargc, start_index, ncaptures;
 	bool splat, splatsplat;
 	int condition_start; // if `-1`, there is no condition.
+	int return_genus_start; // if `-1`, there is no return genus.
 	struct sq_journey_argument *pargv, *kwargv;
+	struct sq_journey_capture *captures;
 	struct sq_codeblock code;
 };
 
 struct sq_journey {
 	SQ_VALUE_ALIGN char *name;
-	unsigned refcount, npatterns;
+	unsigned refcount, npatterns, ncaptures;
 	struct sq_program *program;
 	bool is_method;
 
 	struct sq_journey_pattern *patterns;
+
+	// The values a closure captured from the journey it was created within. This is
+	// `NULL` for journeys that aren't closures; closures share `name` and `patterns`
+	// with the journey they were created from.
+	sq_value *captures;
 };
 
 void sq_journey_deallocate(struct sq_journey *journey);
@@ -71,4 +100,31 @@ static inline sq_value sq_journey_run_deprecated(const struct sq_journey *journe
 
 void sq_journey_dump(FILE *, const struct sq_journey *journey);
 
+/** Prints a listing of `code`'s bytecode to `out`, one instruction per line.
+ *
+ * Each instruction is prefixed with its offset; locals are printed as `$N`,
+ * constants as `#N` followed by their value, and jump targets as `=> POS`.
+ * Globals are printed as `@N`, followed by their name if `global_names` is
+ * not `NULL`.
+ */
+void sq_codeblock_disassemble(FILE *out, const struct sq_codeblock *code, const char *const *global_names);
+
+/** Checks that every instruction in `code` is well-formed, and that every jump
+ * lands on the start of an instruction (or the very end of the code).
+ *
+ * Returns `NULL` if so; otherwise, returns a description of the problem and
+ * stores the offset of the offending instruction in `offset`. Global indices
+ * aren't checked, as only the enclosing program knows how many there are.
+ */
+const char *sq_codeblock_verify(const struct sq_codeblock *code, unsigned *offset);
+
+/** Runs a peephole optimizer over `pattern`'s bytecode.
+ *
+ * This removes instructions which have no effect, such as moving a local to
+ * itself or loading a value that's immediately overwritten. Jump targets, as
+ * well as `pattern`'s start indices, are updated to account for the removed
+ * instructions.
+ */
+void sq_journey_pattern_optimize(struct sq_journey_pattern *pattern);
+
 #endif /* !SQ_FUNCTION_H */
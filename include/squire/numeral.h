@@ -5,12 +5,18 @@
 #include <stdbool.h>
 
 typedef int64_t sq_numeral;
+typedef double sq_decimal;
 
 struct sq_text;
 
+// The largest magnitude that `sq_numeral_to_roman` writes out in roman numerals;
+// anything beyond this would just be a wall of `M`s, so it's written in arabic.
+#define SQ_NUMERAL_ROMAN_MAX 100000
+
 sq_numeral sq_roman_to_numeral(const char *input, const char **output);
 struct sq_text *sq_numeral_to_roman(sq_numeral numeral);
 struct sq_text *sq_numeral_to_arabic(sq_numeral numeral);
+struct sq_text *sq_decimal_to_text(sq_decimal decimal);
 
 extern struct sq_text sq_text_zero;
 
@@ -7,6 +7,7 @@
 #include <squire/other/external.h>
 #include <squire/other/kingdom.h>
 #include <squire/other/builtin_journey.h>
+#include <squire/other/range.h>
 
 #include <assert.h>
 
@@ -18,7 +19,8 @@ struct sq_other {
 		SQ_OK_BUILTIN_JOURNEY,
 		SQ_OK_EXTERNAL,
 		SQ_OK_KINGDOM,
-		SQ_OK_ENVOY
+		SQ_OK_ENVOY,
+		SQ_OK_RANGE
 	} kind;
 
 	union {
@@ -27,11 +29,12 @@ struct sq_other {
 		struct sq_external external;
 		struct sq_kingdom kingdom;
 		struct sq_envoy envoy;
+		struct sq_range range;
 	};
 };
 
 static inline enum sq_other_kind sq_other_kindof(const struct sq_other *other) {
-	assert(((char) (size_t) other & 7) <= SQ_OK_ENVOY);
+	assert(((char) (size_t) other & 7) <= SQ_OK_RANGE);
 	return (enum sq_other_kind) ((char) (size_t) other & 7);
 }
 
@@ -60,6 +63,11 @@ static inline struct sq_envoy *sq_other_as_envoy(struct sq_other *other) {
 	return &other->envoy;
 }
 
+static inline struct sq_range *sq_other_as_range(struct sq_other *other) {
+	assert(other->kind == SQ_OK_RANGE);
+	return &other->range;
+}
+
 void sq_other_dump(FILE *out, const struct sq_other *other);
 void sq_other_deallocate(struct sq_other *other);
 const char *sq_other_typename(const struct sq_other *other);
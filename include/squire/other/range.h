@@ -0,0 +1,19 @@
+#ifndef SQ_RANGE_H
+#define SQ_RANGE_H
+
+#include <squire/value.h>
+
+// The numerals from `start` up to, but not including, `stop`. Ranges whose
+// `stop` isn't after their `start` are empty.
+struct sq_range {
+	sq_numeral start, stop;
+};
+
+struct sq_other *sq_range_new(sq_numeral start, sq_numeral stop);
+void sq_range_dump(FILE *out, const struct sq_range *range);
+struct sq_text *sq_range_to_text(const struct sq_range *range);
+sq_numeral sq_range_length(const struct sq_range *range);
+bool sq_range_contains(const struct sq_range *range, sq_value value);
+sq_value sq_range_get_attr(const struct sq_range *range, const char *attr);
+
+#endif /* !SQ_RANGE_H */
@@ -2,9 +2,41 @@
 #define SQ_PARSE_OLD_H
 
 #include <squire/journey.h>
+#include <squire/token.h>
 
 struct statements *sq_parse_statements(const char *stream);
 
+enum sq_parse_status {
+	SQ_PARSE_COMPLETE,
+	SQ_PARSE_INCOMPLETE,
+};
+
+/** Parses `stream`, which may be only part of a program, such as in a REPL.
+ *
+ * If `stream` ends partway through a statement (eg `if x {`), then
+ * `SQ_PARSE_INCOMPLETE` is returned, and more input should be appended before
+ * trying again. Otherwise, the parsed statements are stored in `stmts` and
+ * `SQ_PARSE_COMPLETE` is returned. Any other parse errors, including a token
+ * left over after the statements (eg the `)` in `x = I )`), are thrown as usual.
+ */
+enum sq_parse_status sq_parse_partial_statements(const char *stream, struct statements **stmts);
+
+// A syntax error found by `sq_parse_diagnose`.
+struct sq_parse_diagnostic {
+	struct sq_position position;
+	enum sq_parse_error_code code;
+	char *message; // prefixed with the line and column, just like the exceptions `sq_parse_error` throws.
+};
+
+/** Parses `stream`, collecting every syntax error instead of stopping at the first one.
+ *
+ * After an error, parsing resumes after the end of the statement it was in: the next `;`,
+ * newline, or unmatched `}`. The errors are stored in `diagnostics`, and their amount is
+ * returned; the caller must free the array and each message. The statements themselves are
+ * discarded, as they may be incomplete.
+ */
+unsigned sq_parse_diagnose(const char *stream, struct sq_parse_diagnostic **diagnostics);
+
 struct statements {
 	unsigned len;
 	struct statement **stmts;
@@ -17,6 +49,7 @@ struct statement {
 		SQ_PS_SJOURNEY,
 		SQ_PS_STRYCATCH,
 		SQ_PS_STHROW,
+		SQ_PS_SASSERT,
 		SQ_PS_SRETURN,
 
 		SQ_PS_SGLOBAL,
@@ -24,6 +57,8 @@ struct statement {
 
 		SQ_PS_SIF,
 		SQ_PS_SWHILE,
+		SQ_PS_SFOR,
+		SQ_PS_SLOOPCONTROL,
 		SQ_PS_SLABEL,
 		SQ_PS_SCOMEFROM,
 		SQ_PS_STHENCE,
@@ -39,10 +74,13 @@ struct statement {
 		struct journey_declaration *jdecl;
 		struct if_statement *ifstmt;
 		struct while_statement *wstmt;
+		struct for_statement *forstmt;
+		struct loop_control *loopctl;
 		struct return_statement *rstmt;
 		struct trycatch_statement *tcstmt;
 		struct switch_statement *sw_stmt;
 		struct expression *throwstmt;
+		struct assert_statement *assertstmt;
 		char *label, *comefrom, *thence;
 		struct expression *expr;
 	};
@@ -73,6 +111,14 @@ struct kingdom_declaration {
 struct scope_declaration {
 	char *name;
 	struct expression *value; // can be null
+
+	// for `nigh a, b, *rest = book`, the names after the first, and `rest` (which can be null).
+	// `names` is null when it's not destructuring.
+	unsigned nnames;
+	char **names, *rest;
+
+	// whether it was declared with `sworn`, and so can't be reassigned.
+	bool sworn;
 };
 
 struct form_declaration {
@@ -134,14 +180,32 @@ struct switch_statement {
 };
 
 struct while_statement {
+	char *label; // NULL when the loop isn't labeled
 	struct expression *cond;
 	struct statements *body;
+	struct statements *alas; // run if the loop finishes without a `flee`; can be null
+};
+
+struct for_statement {
+	char *label; // NULL when the loop isn't labeled
+	char *variable;
+	struct expression *iterable;
+	struct statements *body;
+};
+
+struct loop_control {
+	bool is_onward; // ie `onward` (continue) instead of `flee` (break)
+	char *label; // NULL for the innermost loop
 };
 
 struct return_statement {
 	struct expression *value;
 };
 
+struct assert_statement {
+	struct expression *cond, *message; // message is NULL when not given
+};
+
 struct trycatch_statement {
 	struct statements *try, *catch;
 	char *exception;
@@ -153,6 +217,7 @@ struct expression {
 		SQ_PS_EASSIGN,
 		SQ_PS_EARRAY_ASSIGN,
 		SQ_PS_EMATH,
+		SQ_PS_ETERNARY,
 	} kind;
 
 	union {
@@ -160,13 +225,25 @@ struct expression {
 		struct assignment *asgn;
 		struct index_assign *ary_asgn;
 		struct bool_expression *math;
+		struct ternary *ternary;
 	};
 };
 
+struct ternary {
+	struct bool_expression *cond;
+	struct expression *iftrue, *iffalse;
+};
+
+struct keyword_argument {
+	char *name;
+	struct expression *value;
+};
+
 struct function_call_old {
 	struct variable_old *func;
-	unsigned arglen;
+	unsigned arglen, kwargc;
 	struct expression **args;
+	struct keyword_argument *kwargs;
 };
 
 struct field_access {
@@ -177,11 +254,22 @@ struct field_access {
 struct function_call {
 	struct primary *soul;
 	char *field; // is NULL when is a journey
-	unsigned argc;
+	unsigned argc, kwargc;
 	struct expression **args;
+	struct keyword_argument *kwargs;
 };
 
 struct assignment {
+	enum {
+		SQ_PS_ASGN_PLAIN,
+		SQ_PS_ASGN_ADD,
+		SQ_PS_ASGN_SUB,
+		SQ_PS_ASGN_MUL,
+		SQ_PS_ASGN_DIV,
+		SQ_PS_ASGN_MOD,
+		SQ_PS_ASGN_POW,
+	} kind;
+
 	struct variable_old *var;
 	struct expression *expr;
 };
@@ -216,19 +304,19 @@ struct eql_expression {
 };
 
 struct cmp_expression {
-	enum { SQ_PS_CADD, SQ_PS_CLTH, SQ_PS_CLEQ, SQ_PS_CGTH, SQ_PS_CGEQ, SQ_PS_CCMP } kind;
+	enum { SQ_PS_CADD, SQ_PS_CLTH, SQ_PS_CLEQ, SQ_PS_CGTH, SQ_PS_CGEQ, SQ_PS_CCMP, SQ_PS_CRANGE } kind;
 	struct add_expression *lhs;
 	struct cmp_expression *rhs; // may be NULL.
 };
 
 struct add_expression {
-	enum { SQ_PS_AMUL, SQ_PS_AADD, SQ_PS_ASUB } kind;
+	enum { SQ_PS_AMUL, SQ_PS_AADD, SQ_PS_ASUB, SQ_PS_ABOR, SQ_PS_ABXOR } kind;
 	struct mul_expression *lhs;
 	struct add_expression *rhs; // may be NULL.
 };
 
 struct mul_expression {
-	enum { SQ_PS_MPOW, SQ_PS_MMUL, SQ_PS_MDIV, SQ_PS_MMOD } kind;
+	enum { SQ_PS_MPOW, SQ_PS_MMUL, SQ_PS_MDIV, SQ_PS_MMOD, SQ_PS_MBAND, SQ_PS_MSHL, SQ_PS_MSHR } kind;
 	struct pow_expression *lhs;
 	struct mul_expression *rhs; // may be NULL.
 };
@@ -241,7 +329,10 @@ struct pow_expression {
 
 struct unary_expression {
 	enum { SQ_PS_UPRIMARY, SQ_PS_UNEG, SQ_PS_UNOT } kind;
-	struct primary *rhs;
+	union {
+		struct primary *rhs; // for `SQ_PS_UPRIMARY`
+		struct unary_expression *operand; // for `SQ_PS_UNEG` and `SQ_PS_UNOT`, so they can be nested.
+	};
 };
 
 struct book {
@@ -259,6 +350,7 @@ struct primary {
 		SQ_PS_PPAREN,
 		SQ_PS_PLAMBDA,
 		SQ_PS_PNUMERAL,
+		SQ_PS_PDECIMAL,
 		SQ_PS_PTEXT,
 		SQ_PS_PVERACITY,
 		SQ_PS_PNI,
@@ -275,6 +367,7 @@ struct primary {
 		struct expression *expr;
 		struct journey_declaration *lambda;
 		sq_numeral numeral;
+		sq_decimal decimal;
 		struct sq_text *text;
 		sq_veracity veracity;
 		struct variable_old *variable_old;
@@ -2,15 +2,57 @@
 #define SQ_PROGRAM_H
 
 #include <squire/value.h>
+#include <stdio.h>
 
 struct sq_program {
 	unsigned nglobals;
 	sq_value *globals;
+	char **global_names;
 	struct sq_journey *main;
+
+	// Where `proclaim`, `proclaimn`, and `dump` write to. `sq_program_compile`
+	// sets this to `stdout`, but it can be changed afterwards to redirect output.
+	FILE *output;
+
+	// Where `inquire` reads lines from. Like `output`, this is `stdin` by default.
+	FILE *input;
+
+	// How many instructions the program may run before an exception is thrown, so
+	// that untrusted programs can't run forever. `0`, the default, means no limit.
+	// `steps` is how many have been run so far.
+	unsigned long max_steps, steps;
 };
 
+struct sq_args;
+
+/** Registers `func` as a builtin journey called `name`, which takes `nargs` arguments.
+ *
+ * Builtins are added to the globals of every program compiled afterwards, and
+ * can be called just like any other journey.
+ */
+void sq_program_register_builtin(const char *name, unsigned nargs, sq_value (*func)(struct sq_args args));
+
+// Whether `sq_program_compile` optimizes the bytecode it generates. This is on
+// by default, but can be disabled to see exactly what the compiler emitted.
+extern bool sq_program_optimize;
+
 void sq_program_compile(struct sq_program *program, const char *stream);
 void sq_program_run(struct sq_program *program, unsigned argc, const char **argv);
 void sq_program_finish(struct sq_program *program);
 
+/** Clears everything left over from a previous `sq_program_run`, so `program` can be run again.
+ *
+ * The step count, the journeys being run, the exception handlers, and the last
+ * exception are all reset. Globals are kept, as are `output`, `input`, and `max_steps`.
+ * This is needed after an exception escapes a run, before running or calling anything else.
+ */
+void sq_program_reset(struct sq_program *program);
+
+/** Calls the global journey called `name` with `args`, returning its result.
+ *
+ * An exception is thrown if there's no such global, or if it's not a journey.
+ * This should only be used after `sq_program_run`.
+ */
+sq_value sq_program_call(struct sq_program *program, const char *name, struct sq_args args);
+
 #endif /* !SQ_PROGRAM_H */
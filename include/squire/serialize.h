@@ -0,0 +1,67 @@
+#ifndef SQ_SERIALIZE_H
+#define SQ_SERIALIZE_H
+
+#include <squire/journey.h>
+#include <stddef.h>
+
+#define SQ_SERIALIZE_MAGIC "SQBC"
+#define SQ_SERIALIZE_VERSION 1
+
+/*
+ * The serialized format of a codeblock is (all integers are little-endian):
+ *
+ *   magic    "SQBC"
+ *   version  u8
+ *   nlocals  u32
+ *   codelen  u32
+ *   nconsts  u32
+ *   bytecode codelen u32s
+ *   consts   nconsts tagged constants.
+ *
+ * Each constant is a one-byte `enum sq_serialize_tag`, followed by its payload:
+ * nothing for ni, a u8 for veracities, an i64 for numerals (which must fit in
+ * `64 - SQ_VSHIFT` bits), the u64 bits of the double for decimals, a u32 length followed by that many bytes for texts,
+ * and a u32 length followed by that many tagged values for books. Values on their own (see `sq_value_serialize`) are
+ * encoded the same way as constants, without any header.
+ */
+enum sq_serialize_tag {
+	SQ_SERIALIZE_NI       = 0x00,
+	SQ_SERIALIZE_VERACITY = 0x01,
+	SQ_SERIALIZE_NUMERAL  = 0x02,
+	SQ_SERIALIZE_TEXT     = 0x03,
+	SQ_SERIALIZE_BOOK     = 0x04,
+	SQ_SERIALIZE_DECIMAL  = 0x05,
+};
+
+// How deeply books can be nested within a serialized value.
+#define SQ_SERIALIZE_MAX_DEPTH 256
+
+/** Encodes `code` into a newly-allocated buffer, storing its size in `length`.
+ *
+ * Only ni, veracity, numeral, decimal, text, and book constants can be encoded; an
+ * exception is thrown if `code` contains any others.
+ */
+unsigned char *sq_codeblock_serialize(const struct sq_codeblock *code, size_t *length);
+
+/** Decodes the `length` bytes at `bytes` into `code`.
+ *
+ * An exception is thrown if `bytes` isn't a valid serialized codeblock: if it
+ * has the wrong magic or version, is truncated, has trailing data, or if the
+ * bytecode itself is malformed (see `sq_codeblock_verify`).
+ */
+void sq_codeblock_deserialize(struct sq_codeblock *code, const unsigned char *bytes, size_t length);
+
+/** Encodes `value` into a newly-allocated buffer, storing its size in `length`.
+ *
+ * Only ni, veracities, numerals, decimals, texts, and books of them can be encoded; an
+ * exception is thrown for anything else, such as journeys or forms.
+ */
+unsigned char *sq_value_serialize(sq_value value, size_t *length);
+
+/** Decodes the `length` bytes at `bytes` into a value.
+ *
+ * An exception is thrown if `bytes` isn't exactly one serialized value.
+ */
+sq_value sq_value_deserialize(const unsigned char *bytes, size_t length);
+
+#endif /* !SQ_SERIALIZE_H */
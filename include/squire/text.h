@@ -45,6 +45,43 @@ static inline void sq_text_free(struct sq_text *text) {
 		sq_text_dealloc(text);
 }
 
+// Gets the `index`th (one-based) utf-8 character of `text`; negative indices
+// count from the end. Returns `NULL` if `index` is out of bounds.
+struct sq_text *sq_text_char_at(const struct sq_text *text, sq_numeral index);
+
+// Gets up to `count` utf-8 characters of `text`, starting at the `start`th
+// (one-based) one; negative starts count from the end. Returns an empty text if
+// `start` is out of bounds.
+struct sq_text *sq_text_substr(const struct sq_text *text, sq_numeral start, sq_numeral count);
+
+// Returns a copy of `text` with every character upper- or lowercased.
+struct sq_text *sq_text_upper(const struct sq_text *text);
+struct sq_text *sq_text_lower(const struct sq_text *text);
+
+// Returns a copy of `text` without its leading whitespace (if `start`) and its
+// trailing whitespace (if `end`). Any unicode whitespace is removed, not just ascii.
+struct sq_text *sq_text_trim(const struct sq_text *text, bool start, bool end);
+
+// Splits `text` into a book of the pieces between each occurrence of `sep`, which
+// mustn't be empty. Joining them with `sep` gives back the original text.
+struct sq_book *sq_text_split(const struct sq_text *text, const struct sq_text *sep);
+
+// Returns a copy of `text` with every occurrence of `from`, which mustn't be empty,
+// replaced with `to`. Occurrences are found left-to-right, and don't overlap.
+struct sq_text *sq_text_replace(const struct sq_text *text, const struct sq_text *from, const struct sq_text *to);
+
+// Whether `text` starts or ends with `affix`.
+bool sq_text_starts_with(const struct sq_text *text, const struct sq_text *affix);
+bool sq_text_ends_with(const struct sq_text *text, const struct sq_text *affix);
+
+// Gets the amount of utf-8 characters in `text`, which may be less than its
+// `length` (which is in bytes).
+unsigned sq_text_nchars(const struct sq_text *text);
+
+// Substitutes `%s`, `%d`, and `%%` within `format` with `args`; if `args` is a
+// book, its pages are used positionally, otherwise it's used for the first.
+struct sq_text *sq_text_format(const struct sq_text *format, sq_value args);
+
 void sq_text_combine(const struct sq_text *lhs, const struct sq_text *rhs);
 void sq_text_sprintf_repr(const struct sq_text *text, char **out, unsigned *len, unsigned *cap, unsigned *pos);
 
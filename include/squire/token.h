@@ -3,6 +3,7 @@
 
 #include <squire/value.h>
 #include <squire/text.h>
+#include <squire/shared.h>
 
 enum sq_token_kind {
 	SQ_TK_UNDEFINED = 0,
@@ -18,6 +19,7 @@ enum sq_token_kind {
 
 	SQ_TK_GLOBAL = 0x20,
 	SQ_TK_LOCAL,
+	SQ_TK_SWORN,
 
 	SQ_TK_IF = 0x30,
 	SQ_TK_ELSE,
@@ -31,6 +33,11 @@ enum sq_token_kind {
 	SQ_TK_CASE,
 	SQ_TK_REJOIN,
 	SQ_TK_KINGDOM,
+	SQ_TK_ASSERT,
+	SQ_TK_BREAK,
+	SQ_TK_CONTINUE,
+	SQ_TK_FOR,
+	SQ_TK_IN,
 	// TODO: `assert` as `challenge`?
 
 	SQ_TK_MACRO_VAR = 0x40,
@@ -43,6 +50,7 @@ enum sq_token_kind {
 	SQ_TK_NUMERAL,
 	SQ_TK_TEXT,
 	SQ_TK_LABEL,
+	SQ_TK_DECIMAL,
 
 	SQ_TK_LBRACE = 0x70,
 	SQ_TK_RBRACE,
@@ -56,7 +64,9 @@ enum sq_token_kind {
 	SQ_TK_COLON,
 	SQ_TK_COLONCOLON,
 	SQ_TK_DOT,
+	SQ_TK_DOTDOT,
 	SQ_TK_ARROW,
+	SQ_TK_QUESTION,
 
 	SQ_TK_EQL = 0x80,
 	SQ_TK_NEQ,
@@ -78,6 +88,11 @@ enum sq_token_kind {
 	SQ_TK_DIV_ASSIGN,
 	SQ_TK_MOD_ASSIGN,
 	SQ_TK_POW_ASSIGN,
+	SQ_TK_BAND,
+	SQ_TK_BOR,
+	SQ_TK_BXOR,
+	SQ_TK_SHL,
+	SQ_TK_SHR,
 
 	SQ_TK_NOT,
 	SQ_TK_NEG,
@@ -89,17 +104,72 @@ enum sq_token_kind {
 	SQ_TK_INDEX_ASSIGN,
 };
 
+// Where something is within the source code. Both fields are one-based.
+struct sq_position {
+	unsigned line, column;
+};
+
 struct sq_token {
 	enum sq_token_kind kind;
+	struct sq_position position;
 	union {
 		sq_numeral numeral;
+		sq_decimal decimal;
 		struct sq_text *text;
 		char *identifier;
 	};
 };
 
 extern const char *sq_stream;
+
+// Whether the tokenizer has reached the end of the stream.
+extern bool sq_stream_exhausted;
 struct sq_token sq_next_token(void);
+
+// Starts tokenizing `stream`, resetting the line and column back to the start.
+void sq_stream_begin(const char *stream);
+
+// Forgets any partially-read token (such as an interpolated text), but keeps the current position.
+// Used to continue tokenizing after a parse error.
+void sq_stream_recover(void);
+
+// Returns the position of `where`, which must point into the current stream.
+struct sq_position sq_stream_position(const char *where);
+
+// The kinds of parse errors, so that tools can tell them apart without looking at
+// their messages. These are stable: new kinds are only ever added to the end.
+enum sq_parse_error_code {
+	SQ_PE_NONE,
+	SQ_PE_UNEXPECTED_CHARACTER,
+	SQ_PE_UNTERMINATED_COMMENT,
+	SQ_PE_UNTERMINATED_QUOTE,
+	SQ_PE_UNTERMINATED_ESCAPE,
+	SQ_PE_INVALID_ESCAPE,
+	SQ_PE_INVALID_NUMERAL,
+	SQ_PE_NUMERAL_TOO_LARGE,
+	SQ_PE_EXPECTED_TOKEN,        // a specific token (such as a `)`) or identifier was missing
+	SQ_PE_UNEXPECTED_TOKEN,
+	SQ_PE_MISSING_EXPRESSION,
+	SQ_PE_INVALID_ARGUMENTS,     // in a call, or in a journey's parameters
+	SQ_PE_DUPLICATE_DECLARATION,
+	SQ_PE_LIMIT_EXCEEDED,        // too many interpolations, fields, patterns, etc.
+	SQ_PE_INVALID_MACRO,
+};
+
+// Returns the name of `code`, eg `"E_UNTERMINATED_ESCAPE"`. Each code has its own name.
+const char *sq_parse_error_code_name(enum sq_parse_error_code code);
+
+// The position and code of the last parse error thrown by `sq_parse_error`.
+extern struct sq_position sq_parse_error_position;
+extern enum sq_parse_error_code sq_parse_error_code;
+
+// Throws an error whose message is prefixed with the line and column of `position`.
+void sq_parse_error(
+	struct sq_position position,
+	enum sq_parse_error_code code,
+	const char *fmt,
+	...
+) SQ_ATTR(cold,noreturn);
 void sq_token_dump(const struct sq_token *token);
 
 #endif /* !SQ_TOKEN_H */
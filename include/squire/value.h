@@ -28,6 +28,7 @@ enum sq_genus_tag {
 	SQ_G_JOURNEY   = 5,
 	SQ_G_BOOK      = 6,
 	SQ_G_CODEX     = 7,
+	SQ_G_DECIMAL   = 8,
 };
 
 #define SQ_VSHIFT 4
@@ -75,6 +76,7 @@ static inline enum sq_genus_tag sq_value_genus_tag(sq_value value) {
 
 #define sq_value_new(x) (_Generic(x, \
 	sq_numeral: sq_value_new_numeral, \
+	sq_decimal: sq_value_new_decimal, \
 	sq_veracity: sq_value_new_veracity, \
 	struct sq_text *: sq_value_new_text, \
 	struct sq_form *: sq_value_new_form, \
@@ -91,6 +93,13 @@ static inline sq_value sq_value_new_numeral(sq_numeral numeral) {
 	return SQ_VMASK(((sq_value) numeral) << SQ_VSHIFT, SQ_G_NUMERAL);
 }
 
+// Decimals are stored inline, so their mantissa is rounded to make room for
+// the tag in its bottom `SQ_VSHIFT` bits.
+static inline sq_value sq_value_new_decimal(sq_decimal decimal) {
+	union { sq_decimal decimal; sq_value bits; } pun = { .decimal = decimal };
+	return SQ_VMASK(SQ_VUNMASK(pun.bits + (1 << (SQ_VSHIFT - 1))), SQ_G_DECIMAL);
+}
+
 static inline sq_value sq_value_new_veracity(sq_veracity veracity) {
 	return veracity ? SQ_YAY : SQ_NAY;
 }
@@ -139,6 +148,10 @@ static inline bool sq_value_is_numeral(sq_value value) {
 	return SQ_VTAG(value) == SQ_G_NUMERAL;
 }
 
+static inline bool sq_value_is_decimal(sq_value value) {
+	return SQ_VTAG(value) == SQ_G_DECIMAL;
+}
+
 static inline bool sq_value_is_veracity(sq_value value) {
 	return value == SQ_YAY || value == SQ_NAY;
 }
@@ -176,6 +189,12 @@ static inline sq_numeral sq_value_as_numeral(sq_value value) {
 	return ((sq_numeral) value) >> SQ_VSHIFT;
 }
 
+static inline sq_decimal sq_value_as_decimal(sq_value value) {
+	assert(sq_value_is_decimal(value));
+	union { sq_value bits; sq_decimal decimal; } pun = { .bits = SQ_VUNMASK(value) };
+	return pun.decimal;
+}
+
 static inline bool sq_value_as_veracity(sq_value value) {
 	assert(sq_value_is_veracity(value));
 	return value == SQ_YAY;
@@ -217,6 +236,17 @@ static inline struct sq_other *sq_value_as_other(sq_value value) {
 	return (struct sq_other *) SQ_VUNMASK(value);
 }
 
+/** The most pages a book, or bytes a text, can grow to.
+ *
+ * Operations that would create anything larger (such as `"x" * M` repeated enough
+ * times) throw an exception instead, so a program can't exhaust memory. The default
+ * is generous, but embedders may lower or raise it before running a program.
+ */
+extern size_t sq_value_max_length;
+
+/** Throws an exception if `length` is larger than `sq_value_max_length`. */
+void sq_value_check_length(size_t length);
+
 sq_value sq_value_clone(sq_value value);
 void sq_value_dump(sq_value value);
 void sq_value_dump_to(FILE *out, sq_value value);
@@ -254,6 +284,14 @@ sq_value sq_value_mul(sq_value lhs, sq_value rhs);
 sq_value sq_value_div(sq_value lhs, sq_value rhs);
 sq_value sq_value_mod(sq_value lhs, sq_value rhs);
 sq_value sq_value_pow(sq_value lhs, sq_value rhs);
+
+// Bitwise operators, which only work on numerals (and imitations which overload them).
+// Shifting by a negative amount, or a left shift which overflows, throws an exception.
+sq_value sq_value_band(sq_value lhs, sq_value rhs);
+sq_value sq_value_bor(sq_value lhs, sq_value rhs);
+sq_value sq_value_bxor(sq_value lhs, sq_value rhs);
+sq_value sq_value_shl(sq_value lhs, sq_value rhs);
+sq_value sq_value_shr(sq_value lhs, sq_value rhs);
 sq_value sq_value_index(sq_value value, sq_value key);
 void sq_value_index_assign(sq_value value, sq_value key, sq_value val);
 sq_value sq_value_call(sq_value soul, struct sq_args args);
@@ -264,6 +302,7 @@ bool sq_value_matches(sq_value formlike, sq_value to_check);
 size_t sq_value_length(sq_value value);
 struct sq_text *sq_value_to_text(sq_value value);
 sq_numeral sq_value_to_numeral(sq_value value);
+sq_decimal sq_value_to_decimal(sq_value value);
 bool sq_value_to_veracity(sq_value value);
 struct sq_book *sq_value_to_book(sq_value value);
 struct sq_codex *sq_value_to_codex(sq_value value);
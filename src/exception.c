@@ -2,6 +2,7 @@
 #include <squire/text.h>
 #include <squire/value.h>
 #include <squire/form.h>
+#include <squire/journey.h>
 
 #include <stdarg.h>
 #include <stdlib.h>
@@ -12,6 +13,7 @@
 jmp_buf exception_handlers[SQ_NUM_EXCEPTION_HANDLERS];
 sq_value exception;
 unsigned current_exception_handler;
+struct sq_backtrace sq_exception_backtrace;
 
 // very basics of an exception with a form. todo: that
 struct sq_form sq_exception_form;
@@ -74,11 +76,53 @@ void sq_throw2(struct sq_form *form, const char *fmt, ...) {
 	sq_throw_value(sq_value_new_text(sq_text_new(message)));
 }
 
+static void record_backtrace(void) {
+	unsigned depth;
+	const char *const *stack = sq_journey_stack(&depth);
+
+	if (sq_exception_backtrace.cap < depth) {
+		sq_exception_backtrace.cap = depth;
+		sq_exception_backtrace.names = xrealloc(sq_exception_backtrace.names,
+			sizeof_array(const char *, depth));
+	}
+
+	for (unsigned i = 0; i < depth; ++i)
+		sq_exception_backtrace.names[i] = stack[depth - i - 1];
+
+	sq_exception_backtrace.len = depth;
+}
+
+#define MAX_BACKTRACE_LINES 20
+
 void sq_throw_value(sq_value value)  {
+	record_backtrace();
+
 	if (!current_exception_handler) {
 		fprintf(stderr, "uncaught exception encountered: ");
 		sq_value_dump_to(stderr, value);
 		putc('\n', stderr);
+
+		// collapse runs of the same journey, so deep recursion doesn't flood stderr.
+		unsigned i = 0, lines = 0;
+		while (i < sq_exception_backtrace.len && lines++ < MAX_BACKTRACE_LINES) {
+			const char *name = sq_exception_backtrace.names[i];
+			unsigned run = 1;
+
+			while (i + run < sq_exception_backtrace.len
+				&& !strcmp(sq_exception_backtrace.names[i + run], name))
+				++run;
+
+			if (run == 1)
+				fprintf(stderr, "\tin '%s'\n", name);
+			else
+				fprintf(stderr, "\tin '%s' (x%u)\n", name, run);
+
+			i += run;
+		}
+
+		if (i < sq_exception_backtrace.len)
+			fprintf(stderr, "\t... %u more\n", sq_exception_backtrace.len - i);
+
 		exit(1);
 	}
 
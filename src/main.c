@@ -1,4 +1,5 @@
 #include <squire/program.h>
+#include <squire/parse.h>
 #include <squire/shared.h>
 
 #include <stdio.h>
@@ -31,12 +32,32 @@ char *read_file(const char *filename) {
 	return contents;
 }
 
+// Reports every syntax error in `filename`, without running it.
+static int check_file(const char *filename) {
+	struct sq_parse_diagnostic *diagnostics;
+	char *contents = read_file(filename);
+	unsigned amnt = sq_parse_diagnose(contents, &diagnostics);
+
+	for (unsigned i = 0; i < amnt; ++i) {
+		fprintf(stderr, "%s: %s [%s]\n", filename, diagnostics[i].message,
+			sq_parse_error_code_name(diagnostics[i].code));
+		free(diagnostics[i].message);
+	}
+
+	free(diagnostics);
+	free(contents);
+	return amnt != 0;
+}
+
 int main(int argc, const char **argv) {
-	if (argc < 3 || (strcmp(argv[1], "-e") && strcmp(argv[1], "-f"))) {
-		fprintf(stderr, "usage: %s (-e 'expr' | -f 'filename')\n", argv[0]);
+	if (argc < 3 || (strcmp(argv[1], "-e") && strcmp(argv[1], "-f") && strcmp(argv[1], "-c"))) {
+		fprintf(stderr, "usage: %s (-e 'expr' | -f 'filename' | -c 'filename')\n", argv[0]);
 		return 1;
 	}
 
+	if (argv[1][1] == 'c')
+		return check_file(argv[2]);
+
 	struct sq_program program;
 
 	if (argv[1][1] == 'e') {
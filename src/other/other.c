@@ -26,6 +26,10 @@ void sq_other_dump(FILE *out, const struct sq_other *other) {
 	case SQ_OK_ENVOY:
 		sq_envoy_dump(out, sq_other_as_envoy((struct sq_other *) other));
 		break;
+
+	case SQ_OK_RANGE:
+		sq_range_dump(out, sq_other_as_range((struct sq_other *) other));
+		break;
 	}
 }
 
@@ -51,6 +55,9 @@ void sq_other_deallocate(struct sq_other *other) {
 	case SQ_OK_ENVOY:
 		sq_envoy_deallocate(sq_other_as_envoy(other));
 		break;
+
+	case SQ_OK_RANGE:
+		break;
 	}
 
 	free(other);
@@ -73,6 +80,8 @@ const char *sq_other_typename(const struct sq_other *other) {
 	case SQ_OK_ENVOY:
 		return "Envoy";
 
+	case SQ_OK_RANGE:
+		return "Range";
 	}
 }
 
@@ -80,6 +89,7 @@ sq_value sq_other_genus(const struct sq_other *other) {
 	static struct sq_text KIND_SCROLL = SQ_TEXT_STATIC("Scroll");
 	static struct sq_text KIND_KINGDOM = SQ_TEXT_STATIC("Kingdom");
 	static struct sq_text KIND_ENVOY = SQ_TEXT_STATIC("Envoy");
+	static struct sq_text KIND_RANGE = SQ_TEXT_STATIC("Range");
 
 	switch (other->kind) {
 	case SQ_OK_SCROLL:
@@ -94,6 +104,9 @@ sq_value sq_other_genus(const struct sq_other *other) {
 	case SQ_OK_ENVOY:
 	case SQ_OK_BUILTIN_JOURNEY:
 		return sq_value_new(&KIND_ENVOY);
+
+	case SQ_OK_RANGE:
+		return sq_value_new(&KIND_RANGE);
 	}
 }
 
@@ -108,6 +121,9 @@ struct sq_text *sq_other_to_text(const struct sq_other *other) {
 	case SQ_OK_KINGDOM:
 		return sq_text_new(strdup(sq_other_as_kingdom((struct sq_other *) other)->name));
 
+	case SQ_OK_RANGE:
+		return sq_range_to_text(sq_other_as_range((struct sq_other *) other));
+
 	case SQ_OK_ENVOY:
 	case SQ_OK_BUILTIN_JOURNEY:
 		todo("SQ_OK_ENVOY to text");
@@ -127,6 +143,7 @@ sq_numeral sq_other_to_numeral(const struct sq_other *other) {
 	case SQ_OK_SCROLL:
 	case SQ_OK_ENVOY:
 	case SQ_OK_BUILTIN_JOURNEY:
+	case SQ_OK_RANGE:
 		sq_throw("cannot convert '%s' to a numeral", sq_other_typename(other));
 	}
 }
@@ -136,6 +153,9 @@ sq_veracity sq_other_to_veracity(const struct sq_other *other) {
 	case SQ_OK_EXTERNAL:
 		return sq_external_to_veracity(sq_other_as_external((struct sq_other *) other));
 
+	case SQ_OK_RANGE:
+		return sq_range_length(sq_other_as_range((struct sq_other *) other)) != 0;
+
 	case SQ_OK_SCROLL:
 	case SQ_OK_KINGDOM:
 	case SQ_OK_ENVOY:
@@ -155,6 +175,9 @@ sq_value sq_other_get_attr(const struct sq_other *other, const char *attr) {
 	case SQ_OK_KINGDOM:
 		return sq_kingdom_get_attr(sq_other_as_kingdom((struct sq_other *) other), attr);
 
+	case SQ_OK_RANGE:
+		return sq_range_get_attr(sq_other_as_range((struct sq_other *) other), attr);
+
 	case SQ_OK_ENVOY:
 	case SQ_OK_BUILTIN_JOURNEY:
 		return sq_envoy_get_attr(sq_other_as_envoy((struct sq_other *) other), attr);
@@ -174,6 +197,7 @@ bool sq_other_set_attr(struct sq_other *other, const char *attr, sq_value value)
 
 	case SQ_OK_SCROLL:
 	case SQ_OK_BUILTIN_JOURNEY:
+	case SQ_OK_RANGE:
 		return false;
 	}
 }
@@ -184,6 +208,9 @@ bool sq_other_matches(const struct sq_other *formlike, sq_value to_check) {
 	case SQ_OK_EXTERNAL:
 		return sq_external_matches(sq_other_as_external((struct sq_other *) formlike), to_check);
 
+	case SQ_OK_RANGE:
+		return sq_range_contains(sq_other_as_range((struct sq_other *) formlike), to_check);
+
 	case SQ_OK_SCROLL:
 	case SQ_OK_KINGDOM:
 	case SQ_OK_ENVOY:
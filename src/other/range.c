@@ -0,0 +1,55 @@
+#include <squire/other/other.h>
+#include <squire/shared.h>
+#include <squire/text.h>
+
+#include <inttypes.h>
+#include <string.h>
+
+struct sq_other *sq_range_new(sq_numeral start, sq_numeral stop) {
+	struct sq_other *other = xmalloc(sizeof(struct sq_other));
+
+	other->refcount = 1;
+	other->kind = SQ_OK_RANGE;
+	other->range.start = start;
+	other->range.stop = stop;
+
+	return other;
+}
+
+void sq_range_dump(FILE *out, const struct sq_range *range) {
+	fprintf(out, "Range(%"PRId64"..%"PRId64")", range->start, range->stop);
+}
+
+struct sq_text *sq_range_to_text(const struct sq_range *range) {
+	struct sq_text *start = sq_numeral_to_text(range->start);
+	struct sq_text *stop = sq_numeral_to_text(range->stop);
+	char *text = xmalloc(start->length + stop->length + 3);
+
+	sprintf(text, "%s..%s", start->ptr, stop->ptr);
+	sq_text_free(start);
+	sq_text_free(stop);
+
+	return sq_text_new(text);
+}
+
+sq_numeral sq_range_length(const struct sq_range *range) {
+	return range->start < range->stop ? range->stop - range->start : 0;
+}
+
+bool sq_range_contains(const struct sq_range *range, sq_value value) {
+	if (!sq_value_is_numeral(value))
+		return false;
+
+	sq_numeral numeral = sq_value_as_numeral(value);
+	return range->start <= numeral && numeral < range->stop;
+}
+
+sq_value sq_range_get_attr(const struct sq_range *range, const char *attr) {
+	if (!strcmp(attr, "start"))
+		return sq_value_new(range->start);
+
+	if (!strcmp(attr, "stop"))
+		return sq_value_new(range->stop);
+
+	return SQ_UNDEFINED;
+}
@@ -9,6 +9,7 @@ const char *sq_interrupt_repr(enum sq_interrupt interrupt) {
 	case SQ_INT_TOBOOK: return "SQ_INT_TOBOOK";
 	case SQ_INT_TOCODEX: return "SQ_INT_TOCODEX";
 	case SQ_INT_KINDOF: return "SQ_INT_KINDOF";
+	case SQ_INT_KINDNAME: return "SQ_INT_KINDNAME";
 
 	case SQ_INT_PRINT: return "SQ_INT_PRINT";
 	case SQ_INT_PRINTLN: return "SQ_INT_PRINTLN";
@@ -20,11 +21,26 @@ const char *sq_interrupt_repr(enum sq_interrupt interrupt) {
 
 	case SQ_INT_SUBSTR: return "SQ_INT_SUBSTR";
 	case SQ_INT_LENGTH: return "SQ_INT_LENGTH";
+	case SQ_INT_UPPER: return "SQ_INT_UPPER";
+	case SQ_INT_LOWER: return "SQ_INT_LOWER";
+	case SQ_INT_TRIM: return "SQ_INT_TRIM";
+	case SQ_INT_TRIM_START: return "SQ_INT_TRIM_START";
+	case SQ_INT_TRIM_END: return "SQ_INT_TRIM_END";
+	case SQ_INT_SPLIT: return "SQ_INT_SPLIT";
+	case SQ_INT_JOIN: return "SQ_INT_JOIN";
+	case SQ_INT_STARTS_WITH: return "SQ_INT_STARTS_WITH";
+	case SQ_INT_ENDS_WITH: return "SQ_INT_ENDS_WITH";
+	case SQ_INT_REPLACE: return "SQ_INT_REPLACE";
 
 	case SQ_INT_CODEX_NEW: return "SQ_INT_CODEX_NEW";
 	case SQ_INT_BOOK_NEW: return "SQ_INT_BOOK_NEW";
 	case SQ_INT_ARRAY_INSERT: return "SQ_INT_ARRAY_INSERT";
 	case SQ_INT_ARRAY_DELETE: return "SQ_INT_ARRAY_DELETE";
+	case SQ_INT_RANGE_NEW: return "SQ_INT_RANGE_NEW";
+	case SQ_INT_JOURNEY_CAPTURE: return "SQ_INT_JOURNEY_CAPTURE";
+	case SQ_INT_ARRAY_CONTAINS: return "SQ_INT_ARRAY_CONTAINS";
+	case SQ_INT_ARRAY_INDEX_OF: return "SQ_INT_ARRAY_INDEX_OF";
+	case SQ_INT_ARRAY_UNPACK: return "SQ_INT_ARRAY_UNPACK";
 
 	case SQ_INT_ARABIC: return "SQ_INT_ARABIC";
 	case SQ_INT_ROMAN: return "SQ_INT_ROMAN";
@@ -56,10 +72,14 @@ const char *sq_opcode_repr(enum sq_opcode opcode) {
 	case SQ_OC_COMEFROM: return "SQ_OC_COMEFROM";
 	case SQ_OC_TRYCATCH: return "SQ_OC_TRYCATCH";
 	case SQ_OC_THROW: return "SQ_OC_THROW";
+	case SQ_OC_KWCALL: return "SQ_OC_KWCALL";
 	case SQ_OC_POPTRYCATCH: return "SQ_OC_POPTRYCATCH";
 #ifndef SQ_NMOON_JOKE
 	case SQ_OC_WERE_JMP: return "SQ_OC_WERE_JMP";
 #endif /* !SQ_NMOON_JOKE */
+	case SQ_OC_TAILCALL: return "SQ_OC_TAILCALL";
+	case SQ_OC_ITER_START: return "SQ_OC_ITER_START";
+	case SQ_OC_ITER_NEXT: return "SQ_OC_ITER_NEXT";
 	
 	case SQ_OC_NOT: return "SQ_OC_NOT";
 	case SQ_OC_NEG: return "SQ_OC_NEG";
@@ -79,6 +99,11 @@ const char *sq_opcode_repr(enum sq_opcode opcode) {
 	case SQ_OC_INDEX: return "SQ_OC_INDEX";
 	case SQ_OC_INDEX_ASSIGN: return "SQ_OC_INDEX_ASSIGN";
 	case SQ_OC_MATCHES: return "SQ_OC_MATCHES";
+	case SQ_OC_BAND: return "SQ_OC_BAND";
+	case SQ_OC_BOR: return "SQ_OC_BOR";
+	case SQ_OC_BXOR: return "SQ_OC_BXOR";
+	case SQ_OC_SHL: return "SQ_OC_SHL";
+	case SQ_OC_SHR: return "SQ_OC_SHR";
 	
 	case SQ_OC_CLOAD: return "SQ_OC_CLOAD";
 	case SQ_OC_GLOAD: return "SQ_OC_GLOAD";
@@ -4,13 +4,14 @@
 #include <squire/parse.h>
 #include <squire/form.h>
 #include <squire/text.h>
+#include <squire/other/other.h>
 
 #include <string.h>
 #include <errno.h>
 
 struct sq_program *program;
+bool sq_program_optimize = true;
 
-#define CURRENT_INDEX_PTR(code) (&(code)->bytecode[(code)->codelen].index)
 struct {
 	unsigned len, cap;
 	struct global {
@@ -19,35 +20,92 @@ struct {
 	} *ary;
 } globals;
 
+// builtin journeys registered by embedders, which are added to every program's globals.
+struct {
+	unsigned len, cap;
+	sq_value *ary;
+} builtins;
+
 #define MAX_COMEFROMS 16
 
+// the variables a journey expression captures from the code it's nested within.
+struct captures {
+	unsigned cap, len;
+	struct capture {
+		char *name;
+		unsigned source; // the local in the enclosing code that's captured
+	} *ary;
+};
+
 struct sq_code {
 	unsigned codecap, codelen;
 	union sq_bytecode *bytecode;
 
 	unsigned nlocals;
 
+	// locals used for temporary values, which are reused after each statement.
+	struct {
+		unsigned cap, len, used;
+		unsigned *ary;
+	} temps;
+
 	struct {
 		unsigned cap, len;
 		struct label {
 			char *name;
-			int *length, indices[MAX_COMEFROMS];
+			int position, indices[MAX_COMEFROMS]; // position is -1 until declared
 		} *ary;
 	} labels;
 
+	struct {
+		unsigned cap, len;
+		struct loop {
+			char *label;
+			unsigned condition_label, trycatch_depth;
+			unsigned nflees, fleecap, *flees; // jumps to patch once the loop ends
+		} *ary;
+	} loops;
+
+	unsigned trycatch_depth;
+
+	// where the most recent `CALL` starts and ends, so `reward`s of it can be
+	// turned into tail calls. `last_call_end` is `0` if there's been no call.
+	unsigned last_call, last_call_end;
+	bool can_tail_call; // false for journeys with return genera, as they need to coerce the result.
+
 	struct {
 		unsigned cap, len;
 
 		struct local {
 			char *name;
-			unsigned index;
+			unsigned index, depth; // `depth` is the block a `nigh` was declared in; `0` is journey-wide.
+			bool assigned; // false if it's only ever been read, in which case it's undefined.
+			bool sworn; // true if it was declared with `sworn`, and so can't be reassigned.
 		} *ary;
 	} vars;
 
+	unsigned scope_depth;
+
+	// for journey expressions, the code they're nested within and what's been captured from it.
+	// `enclosing` is `NULL` for journeys that can't capture anything (eg `journey` declarations).
+	struct sq_code *enclosing;
+	struct captures *captures;
+
+	struct {
+		unsigned cap, len;
+		struct sq_journey_capture *ary;
+	} capture_locals;
+
 	struct {
 		unsigned cap, len;
 		sq_value *ary;
 	} consts;
+
+	// maps constants to their index in `consts`, so they're only declared once.
+	struct {
+		unsigned cap, len;
+		int *buckets; // -1 when empty
+	} constmap;
 };
 
 #define RESIZE(cap, len, pos, type) \
@@ -97,10 +155,92 @@ static void set_target_to_codelen(struct sq_code *code, unsigned target) {
 }
 
 static unsigned next_local(struct sq_code *code) {
-	return code->nlocals++;
+	if (code->temps.used == code->temps.len) {
+		RESIZE(temps.cap, temps.len, temps.ary, unsigned);
+		code->temps.ary[code->temps.len++] = code->nlocals++;
+	}
+
+	return code->temps.ary[code->temps.used++];
+}
+
+// Only veracities, ni, numerals, decimals, and texts are ever reused; everything else
+// (eg journeys) is always given its own constant.
+static bool hash_constant(sq_value value, unsigned long *hash) {
+	switch (sq_value_genus_tag(value)) {
+	// decimals are compared bitwise, so `0.0` and `-0.0` are kept apart.
+	case SQ_G_NUMERAL:
+	case SQ_G_DECIMAL:
+		*hash = value >> SQ_VSHIFT;
+		return true;
+
+	case SQ_G_TEXT: {
+		const struct sq_text *text = sq_value_as_text(value);
+
+		*hash = 525201411107845655UL;
+		for (unsigned i = 0; i < text->length; ++i)
+			*hash = (*hash ^ (unsigned char) text->ptr[i]) * 0x5bd1e9955bd1e995;
+
+		return true;
+	}
+
+	case SQ_G_OTHER:
+		*hash = value;
+		return value == SQ_YAY || value == SQ_NAY || value == SQ_NI;
+
+	default:
+		return false;
+	}
+}
+
+// Structural equality for the constant pool. This is deliberately stricter than `sq_value_eql`:
+// a numeral never merges with an equal decimal (`I` and `1.0` stay apart), and decimals only
+// merge when their bits are identical.
+static bool constants_are_equal(sq_value lhs, sq_value rhs) {
+	if (sq_value_genus_tag(lhs) != sq_value_genus_tag(rhs))
+		return false;
+
+	if (!sq_value_is_text(lhs))
+		return lhs == rhs;
+
+	const struct sq_text *ltext = sq_value_as_text(lhs), *rtext = sq_value_as_text(rhs);
+
+	return ltext->length == rtext->length && !memcmp(ltext->ptr, rtext->ptr, ltext->length);
+}
+
+static void insert_constmap(struct sq_code *code, unsigned long hash, unsigned index) {
+	unsigned bucket = hash & (code->constmap.cap - 1);
+
+	while (code->constmap.buckets[bucket] != -1)
+		bucket = (bucket + 1) & (code->constmap.cap - 1);
+
+	code->constmap.buckets[bucket] = index;
+	++code->constmap.len;
+}
+
+static void expand_constmap(struct sq_code *code) {
+	int *buckets = code->constmap.buckets;
+	unsigned cap = code->constmap.cap;
+	unsigned long hash;
+
+	code->constmap.len = 0;
+	code->constmap.cap *= 2;
+	code->constmap.buckets = xmalloc(sizeof_array(int, code->constmap.cap));
+	memset(code->constmap.buckets, -1, sizeof_array(int, code->constmap.cap));
+
+	for (unsigned i = 0; i < cap; ++i) {
+		if (buckets[i] == -1)
+			continue;
+
+		hash_constant(code->consts.ary[buckets[i]], &hash);
+		insert_constmap(code, hash, buckets[i]);
+	}
+
+	free(buckets);
 }
 
 static unsigned declare_constant(struct sq_code *code, sq_value value) {
+	unsigned long hash;
+
 	if (code->consts.cap == code->consts.len) {
 		code->consts.cap *= 2;
 		code->consts.ary = xrealloc(code->consts.ary, sizeof_array(sq_value, code->consts.cap));
@@ -113,15 +253,30 @@ static unsigned declare_constant(struct sq_code *code, sq_value value) {
 #endif /* SQ_LOG */
 
 	code->consts.ary[code->consts.len] = value;
+
+	if (hash_constant(value, &hash)) {
+		if (code->constmap.cap <= code->constmap.len * 2)
+			expand_constmap(code);
+
+		insert_constmap(code, hash, code->consts.len);
+	}
+
 	return code->consts.len++;
 }
 
 static int lookup_constant(struct sq_code *code, sq_value value) {
+	unsigned long hash;
+
+	if (!hash_constant(value, &hash))
+		return -1;
+
 	// check to see if we've declared the constant before. if so, reuse that.
-	for (unsigned i = 0; i < code->consts.len; ++i) {
-		if (sq_value_eql(code->consts.ary[i], value)) {
-			return -1; // TODO: THIS does not work because of a bug...
-		}
+	for (unsigned bucket = hash & (code->constmap.cap - 1);
+		code->constmap.buckets[bucket] != -1;
+		bucket = (bucket + 1) & (code->constmap.cap - 1)
+	) {
+		if (constants_are_equal(code->consts.ary[code->constmap.buckets[bucket]], value))
+			return code->constmap.buckets[bucket];
 	}
 
 	return -1;
@@ -130,18 +285,15 @@ static int lookup_constant(struct sq_code *code, sq_value value) {
 static unsigned new_constant(struct sq_code *code, sq_value value) {
 	int index = lookup_constant(code, value);
 
-	return (index == -1) ? declare_constant(code, value) : index;
+	return (index == -1) ? declare_constant(code, value) : (unsigned) index;
 }
 
 static unsigned load_constant(struct sq_code *code, sq_value value) {
-	int index = lookup_constant(code, value);
+	unsigned index;
 
-	// if we haven't encountered the value before, load it.
-	if (index == -1) {
-		set_opcode(code, SQ_OC_CLOAD);
-		set_index(code, declare_constant(code, value));
-		set_index(code, index = next_local(code));
-	}
+	set_opcode(code, SQ_OC_CLOAD);
+	set_index(code, new_constant(code, value));
+	set_index(code, index = next_local(code));
 
 	return index;
 }
@@ -189,47 +341,132 @@ static int new_global(const char *name) {
 	return (index == -1) ? declare_global_variable(name, SQ_NI) : index;
 }
 
-static unsigned declare_local_variable(struct sq_code *code, const char *name) {
+static unsigned declare_local_variable(struct sq_code *code, const char *name, unsigned depth) {
 	// reallocate if necessary
 	RESIZE(vars.cap, vars.len, vars.ary, struct local);
 
 	LOG("local[%d]: %s\n", globals.len, name);
 
 	code->vars.ary[code->vars.len].name = strdup(name);
-	return code->vars.ary[code->vars.len++].index = next_local(code);
+	code->vars.ary[code->vars.len].depth = depth;
+	code->vars.ary[code->vars.len].assigned = true;
+	code->vars.ary[code->vars.len].sworn = false;
+	// variables are never reused, so they don't come from `next_local`.
+	return code->vars.ary[code->vars.len++].index = code->nlocals++;
 }
 
-static int lookup_local_variable(struct sq_code *code, const char *name) {
-	// check to see if we've declared the local before
-	for (unsigned i = 0; i < code->vars.len; ++i) {
+static struct local *find_local_variable(struct sq_code *code, const char *name) {
+	// check to see if we've declared the local before. we search backwards so that a `nigh` in an
+	// inner block shadows any outer variables of the same name.
+	for (unsigned i = code->vars.len; i--;) {
 		if (!strcmp(name, code->vars.ary[i].name)) {
-			return code->vars.ary[i].index;
+			return &code->vars.ary[i];
 		}
 	}
 
-	return -1;
+	return NULL;
+}
+
+static int lookup_local_variable(struct sq_code *code, const char *name) {
+	struct local *local = find_local_variable(code, name);
+
+	return local == NULL ? -1 : (int) local->index;
 }
 
 static unsigned new_local_variable(struct sq_code *code, const char *name) {
 	int index = lookup_local_variable(code, name);
 
-	return (index == -1) ? declare_local_variable(code, name) : index;
+	// variables that aren't declared with `nigh` are visible throughout the entire journey.
+	return (index == -1) ? declare_local_variable(code, name, 0) : index;
+}
+
+static void begin_scope(struct sq_code *code) {
+	++code->scope_depth;
+}
+
+// Forget every `nigh` declared within the block that's ending, which uncovers anything they shadowed.
+// Their slots are never handed out again, so nothing else can clobber them.
+static void end_scope(struct sq_code *code) {
+	unsigned len = 0;
+
+	--code->scope_depth;
+
+	for (unsigned i = 0; i < code->vars.len; ++i) {
+		if (code->vars.ary[i].depth <= code->scope_depth)
+			code->vars.ary[len++] = code->vars.ary[i];
+		else
+			free(code->vars.ary[i].name);
+	}
+
+	code->vars.len = len;
 }
 
 
-static int lookup_identifier(struct sq_code *code, const char *name) {
+// Looks `name` up in the code that `code` is nested within. If it's found, it's captured into a
+// new local of `code`, which is returned.
+static int lookup_captured_variable(struct sq_code *code, const char *name) {
+	int source;
+	unsigned index;
+
+	if (code->enclosing == NULL)
+		return -1;
+
+	if ((source = lookup_local_variable(code->enclosing, name)) == -1
+		&& (source = lookup_captured_variable(code->enclosing, name)) == -1
+	) return -1;
+
+	// other patterns of this journey may have already captured the variable.
+	for (index = 0; index < code->captures->len; ++index)
+		if (!strcmp(code->captures->ary[index].name, name))
+			break;
+
+	if (index == code->captures->len) {
+		if (code->captures->len == code->captures->cap)
+			code->captures->ary = xrealloc(code->captures->ary,
+				sizeof_array(struct capture, code->captures->cap = code->captures->cap * 2 + 4));
+
+		code->captures->ary[index].name = strdup(name);
+		code->captures->ary[index].source = source;
+		++code->captures->len;
+	}
+
+	RESIZE(capture_locals.cap, capture_locals.len, capture_locals.ary, struct sq_journey_capture);
+	code->capture_locals.ary[code->capture_locals.len].index = index;
+	code->capture_locals.ary[code->capture_locals.len].local = declare_local_variable(code, name, 0);
+
+	// captured `sworn` variables are still sworn.
+	code->vars.ary[code->vars.len - 1].sworn = find_local_variable(code->enclosing, name)->sworn;
+
+	return code->capture_locals.ary[code->capture_locals.len++].local;
+}
+
+// Finds the local or global that `name` refers to, declaring a new local if there's neither. Locals
+// that are only ever read, never assigned, are reported as undefined once the journey's compiled.
+static int lookup_identifier(struct sq_code *code, const char *name, bool is_assignment) {
+	struct local *local;
 	int index;
-	if ((index = lookup_local_variable(code, name)) != -1)
-		return index;
+
+	if ((local = find_local_variable(code, name)) == NULL && lookup_captured_variable(code, name) != -1)
+		local = find_local_variable(code, name);
+
+	if (local != NULL) {
+		if (is_assignment && local->sworn)
+			die("cannot reassign sworn variable '%s'", name);
+
+		local->assigned |= is_assignment;
+		return local->index;
+	}
 
 	if ((index = lookup_global_variable(name)) != -1)
 		return ~index;
 
-	return new_local_variable(code, name);
+	index = declare_local_variable(code, name, 0);
+	code->vars.ary[code->vars.len - 1].assigned = is_assignment;
+	return index;
 }
 
 static unsigned load_identifier(struct sq_code *code, const char *name) {
-	int index = lookup_identifier(code, name);
+	int index = lookup_identifier(code, name, false);
 
 	if (index < 0) {
 		set_opcode(code, SQ_OC_GLOAD);
@@ -272,6 +509,11 @@ static unsigned compile_expression(struct sq_code *code, struct expression *expr
 static unsigned compile_primary(struct sq_code *code, struct primary *primary);
 static void compile_statements(struct sq_code *code, struct statements *stmts);
 static struct sq_journey *compile_journey(struct journey_declaration *jd, bool is_method);
+static struct sq_journey *compile_nested_journey(
+	struct journey_declaration *jd,
+	struct sq_code *enclosing,
+	struct captures *captures
+);
 
 static void compile_form_declaration(struct sq_code *code, struct form_declaration *fdecl) {
 	struct sq_form *form = sq_form_new(fdecl->name);
@@ -468,10 +710,40 @@ static void compile_if_statement(struct sq_code *code, struct if_statement *ifst
 	free(ifstmt);
 }
 
+static void compile_label_statement(struct sq_code *code, char *label);
+
+// Starts a loop which `onward` jumps back to `condition_label` of. The loop owns `label`.
+static void begin_loop(struct sq_code *code, char *label, unsigned condition_label) {
+	RESIZE(loops.cap, loops.len, loops.ary, struct loop);
+	struct loop *loop = &code->loops.ary[code->loops.len++];
+	loop->label = label;
+	loop->condition_label = condition_label;
+	loop->trycatch_depth = code->trycatch_depth;
+	loop->nflees = 0;
+	loop->fleecap = 4;
+	loop->flees = xmalloc(sizeof_array(unsigned, loop->fleecap));
+}
+
+// Ends the innermost loop, making its `flee`s jump to the current position.
+static void end_loop(struct sq_code *code) {
+	// `loops.ary` may have been reallocated by nested loops.
+	struct loop *loop = &code->loops.ary[--code->loops.len];
+	for (unsigned i = 0; i < loop->nflees; ++i)
+		set_target_to_codelen(code, loop->flees[i]);
+
+	free(loop->flees);
+	free(loop->label);
+}
+
 static void compile_while_statement(struct sq_code *code, struct while_statement *wstmt) {
 	unsigned condition_index, condition_label, finished_label;
 
+	// the label's still usable with `whence`, in addition to `flee` and `onward`.
+	if (wstmt->label)
+		compile_label_statement(code, strdup(wstmt->label));
+
 	condition_label = code->codelen;
+	begin_loop(code, wstmt->label, condition_label);
 
 	condition_index = compile_expression(code, wstmt->cond);
 	set_opcode(code, SQ_OC_JMP_FALSE);
@@ -485,11 +757,104 @@ static void compile_while_statement(struct sq_code *code, struct while_statement
 	set_index(code, condition_label);
 	set_target_to_codelen(code, finished_label);
 
+	// `flee`s skip the `alas` block, so it only runs once the condition's false. The loop's set
+	// aside while it's compiled, so any `flee` or `onward` within it applies to an enclosing loop.
+	if (wstmt->alas) {
+		struct loop loop = code->loops.ary[--code->loops.len];
+		compile_statements(code, wstmt->alas);
+		code->loops.ary[code->loops.len++] = loop;
+	}
+
+	end_loop(code);
 	free(wstmt);
 }
 
+static void compile_for_statement(struct sq_code *code, struct for_statement *fstmt) {
+	unsigned iterable, position, element, next_label, finished_label;
+	int variable;
+
+	if (fstmt->label)
+		compile_label_statement(code, strdup(fstmt->label));
+
+	// copy what we're iterating over, so the body reassigning its variable doesn't affect the loop.
+	iterable = compile_expression(code, fstmt->iterable);
+	set_opcode(code, SQ_OC_MOV);
+	set_index(code, iterable);
+	set_index(code, iterable = next_local(code));
+
+	set_opcode(code, SQ_OC_ITER_START);
+	set_index(code, iterable);
+	set_index(code, position = next_local(code));
+
+	next_label = code->codelen;
+	begin_loop(code, fstmt->label, next_label);
+
+	variable = lookup_identifier(code, fstmt->variable, true);
+	element = 0 <= variable ? (unsigned) variable : next_local(code);
+
+	set_opcode(code, SQ_OC_ITER_NEXT);
+	set_index(code, iterable);
+	set_index(code, position);
+	set_index(code, element);
+	finished_label = code->codelen;
+	set_index(code, 0);
+
+	if (variable < 0) {
+		set_opcode(code, SQ_OC_GSTORE);
+		set_index(code, element);
+		set_index(code, ~variable);
+	}
+
+	compile_statements(code, fstmt->body);
+
+	set_opcode(code, SQ_OC_JMP);
+	set_index(code, next_label);
+	set_target_to_codelen(code, finished_label);
+
+	end_loop(code);
+	free(fstmt->variable);
+	free(fstmt);
+}
+
+static void compile_loop_control_statement(struct sq_code *code, struct loop_control *loopctl) {
+	const char *keyword = loopctl->is_onward ? "onward" : "flee";
+	struct loop *loop = NULL;
+
+	for (unsigned i = code->loops.len; i--;) {
+		if (!loopctl->label || (code->loops.ary[i].label && !strcmp(code->loops.ary[i].label, loopctl->label))) {
+			loop = &code->loops.ary[i];
+			break;
+		}
+	}
+
+	if (!loop) {
+		if (loopctl->label)
+			die("cannot '%s' to '%s': no enclosing loop has that label", keyword, loopctl->label);
+		die("cannot '%s' outside of a loop", keyword);
+	}
+
+	// any `attempt`s we're leaving need to be removed.
+	for (unsigned i = loop->trycatch_depth; i < code->trycatch_depth; ++i)
+		set_opcode(code, SQ_OC_POPTRYCATCH);
+
+	set_opcode(code, SQ_OC_JMP);
+
+	if (loopctl->is_onward) {
+		set_index(code, loop->condition_label);
+	} else {
+		if (loop->nflees == loop->fleecap)
+			loop->flees = xrealloc(loop->flees, sizeof_array(unsigned, loop->fleecap *= 2));
+
+		loop->flees[loop->nflees++] = code->codelen;
+		set_index(code, 0);
+	}
+
+	free(loopctl->label);
+	free(loopctl);
+}
+
 static void compile_return_statement(struct sq_code *code, struct return_statement *rstmt) {
-	unsigned index;
+	unsigned index, start = code->codelen;
 
 	if (rstmt->value == NULL) {
 		index = load_constant(code, SQ_NI);
@@ -497,6 +862,17 @@ static void compile_return_statement(struct sq_code *code, struct return_stateme
 		index = compile_expression(code, rstmt->value);
 	}
 
+	// when rewarding the result of a call, it can reuse our frame instead of
+	// growing the stack. this isn't possible within `attempt`s, as the call's
+	// exceptions need to be caught by them.
+	if (code->can_tail_call && !code->trycatch_depth && code->last_call_end != 0 && start <= code->last_call
+		&& code->last_call_end == code->codelen && code->bytecode[code->codelen - 1].index == index
+	) {
+		--code->codelen; // tail calls don't have a destination
+		code->bytecode[code->last_call].opcode = SQ_OC_TAILCALL;
+		return;
+	}
+
 	set_opcode(code, SQ_OC_RETURN);
 	set_index(code, index);
 }
@@ -554,12 +930,12 @@ static void compile_switch_statement(struct sq_code *code, struct switch_stateme
 			continue;
 		}
 
+		// blank bodies fall through to the next non-blank one.
 		for (unsigned j = 0; j < amnt_of_blank; ++j)
-			jump_to_body_indices[i - j - 1] = code->codelen;
+			set_target_to_codelen(code, jump_to_body_indices[i - j - 1]);
 
 		amnt_of_blank = 0;
 		set_target_to_codelen(code, jump_to_body_indices[i]);
-		jump_to_body_indices[i] = code->codelen;
 
 		compile_statements(code, sw->cases[i].body);
 
@@ -573,7 +949,7 @@ static void compile_switch_statement(struct sq_code *code, struct switch_stateme
 	}
 
 	for (unsigned j = 0; j < amnt_of_blank; ++j)
-		jump_to_body_indices[sw->ncases - j - 1] = code->codelen;
+		set_target_to_codelen(code, jump_to_body_indices[sw->ncases - j - 1]);
 
 	for (unsigned i = 0; i <= sw->ncases; ++i) {
 		if (0 <= jump_to_end_indices[i])
@@ -629,7 +1005,7 @@ static unsigned compile_codex(struct sq_code *code, struct dict *dict) {
 
 
 static unsigned compile_function_call(struct sq_code *code, struct function_call *fncall) {
-	unsigned soul;
+	unsigned soul = 0;
 	enum sq_interrupt interrupt = SQ_INT_UNDEFINED;
 
 #define CHECK_FOR_BUILTIN(name_, interrupt_, argc_) \
@@ -655,11 +1031,24 @@ static unsigned compile_function_call(struct sq_code *code, struct function_call
 		CHECK_FOR_BUILTIN("book",      SQ_INT_TOBOOK, 1);
 		CHECK_FOR_BUILTIN("codex",     SQ_INT_TOCODEX, 1);
 		CHECK_FOR_BUILTIN("genus",     SQ_INT_KINDOF, 1);
+		CHECK_FOR_BUILTIN("kind",      SQ_INT_KINDNAME, 1);
 
 		CHECK_FOR_BUILTIN("length",    SQ_INT_LENGTH, 1); // `fathoms` ? furlong
+		CHECK_FOR_BUILTIN("upper",     SQ_INT_UPPER, 1);
+		CHECK_FOR_BUILTIN("lower",     SQ_INT_LOWER, 1);
+		CHECK_FOR_BUILTIN("trim",      SQ_INT_TRIM, 1);
+		CHECK_FOR_BUILTIN("trim_start", SQ_INT_TRIM_START, 1);
+		CHECK_FOR_BUILTIN("trim_end",  SQ_INT_TRIM_END, 1);
+		CHECK_FOR_BUILTIN("split",     SQ_INT_SPLIT, 2);
+		CHECK_FOR_BUILTIN("join",      SQ_INT_JOIN, 2);
+		CHECK_FOR_BUILTIN("starts_with", SQ_INT_STARTS_WITH, 2);
+		CHECK_FOR_BUILTIN("ends_with", SQ_INT_ENDS_WITH, 2);
+		CHECK_FOR_BUILTIN("replace",   SQ_INT_REPLACE, 3);
 		CHECK_FOR_BUILTIN("substr",    SQ_INT_SUBSTR, 3);
 		CHECK_FOR_BUILTIN("insert",    SQ_INT_ARRAY_INSERT, 3);
 		CHECK_FOR_BUILTIN("delete",    SQ_INT_ARRAY_DELETE, 2); // `slay`?
+		CHECK_FOR_BUILTIN("contains",  SQ_INT_ARRAY_CONTAINS, 2);
+		CHECK_FOR_BUILTIN("index_of",  SQ_INT_ARRAY_INDEX_OF, 2);
 
 		CHECK_FOR_BUILTIN("gamble",    SQ_INT_RANDOM, 0);
 		CHECK_FOR_BUILTIN("roman",     SQ_INT_ROMAN, 1);
@@ -681,12 +1070,18 @@ static unsigned compile_function_call(struct sq_code *code, struct function_call
 
 compile_arguments:;
 
-	unsigned args[fncall->argc];
+	unsigned args[fncall->argc], kwargs[fncall->kwargc + 1];
 
 	for (unsigned i = 0; i < fncall->argc; ++i)
 		args[i] = compile_expression(code, fncall->args[i]);
 
+	for (unsigned i = 0; i < fncall->kwargc; ++i)
+		kwargs[i] = compile_expression(code, fncall->kwargs[i].value);
+
 	if (interrupt != SQ_INT_UNDEFINED) {
+		if (fncall->kwargc)
+			die("keyword arguments cannot be passed to '%s'", fncall->soul->variable);
+
 		set_opcode(code, SQ_OC_INT);
 		set_interrupt(code, interrupt);
 		goto assign_arguments;
@@ -701,14 +1096,18 @@ compile_arguments:;
 		set_index(code, new_constant(code, sq_value_new(sq_text_new(strdup(fncall->field)))));
 		set_index(code, target = next_local(code));
 
-		set_opcode(code, SQ_OC_CALL);
+		code->last_call = code->codelen;
+		set_opcode(code, fncall->kwargc ? SQ_OC_KWCALL : SQ_OC_CALL);
 		set_index(code, target);
 		set_count(code, fncall->argc + 1);
+		if (fncall->kwargc) set_count(code, fncall->kwargc);
 		set_index(code, soul);
 	} else {
-		set_opcode(code, SQ_OC_CALL);
+		code->last_call = code->codelen;
+		set_opcode(code, fncall->kwargc ? SQ_OC_KWCALL : SQ_OC_CALL);
 		set_index(code, soul);
-		set_index(code, fncall->argc);
+		set_count(code, fncall->argc);
+		if (fncall->kwargc) set_count(code, fncall->kwargc);
 	}
 
 assign_arguments:
@@ -716,8 +1115,17 @@ assign_arguments:
 	for (unsigned i = 0; i < fncall->argc; ++i)
 		set_index(code, args[i]);
 
+	for (unsigned i = 0; i < fncall->kwargc; ++i) {
+		set_index(code, new_constant(code, sq_value_new(sq_text_new(strdup(fncall->kwargs[i].name)))));
+		set_index(code, kwargs[i]);
+	}
+
 	unsigned result;
 	set_index(code, result = next_local(code));
+
+	if (interrupt == SQ_INT_UNDEFINED && !fncall->kwargc)
+		code->last_call_end = code->codelen;
+
 	return result;
 }
 
@@ -761,10 +1169,27 @@ static unsigned compile_primary(struct sq_code *code, struct primary *primary) {
 		break;
 
 	case SQ_PS_PLAMBDA: {
-		struct sq_journey *func = compile_journey(primary->lambda, false);
+		struct captures captures = { .cap = 0, .len = 0, .ary = NULL };
+		struct sq_journey *func = compile_nested_journey(primary->lambda, code, &captures);
 		free(primary->lambda);
 
 		result = load_constant(code, sq_value_new(func));
+
+		if (!captures.len)
+			break;
+
+		set_opcode(code, SQ_OC_INT);
+		set_interrupt(code, SQ_INT_JOURNEY_CAPTURE);
+		set_index(code, result);
+		set_count(code, captures.len);
+
+		for (unsigned i = 0; i < captures.len; ++i) {
+			set_index(code, captures.ary[i].source);
+			free(captures.ary[i].name);
+		}
+
+		set_index(code, result = next_local(code));
+		free(captures.ary);
 		break;
 	}
 
@@ -772,6 +1197,10 @@ static unsigned compile_primary(struct sq_code *code, struct primary *primary) {
 		result = load_constant(code, sq_value_new(primary->numeral));
 		break;
 
+	case SQ_PS_PDECIMAL:
+		result = load_constant(code, sq_value_new(primary->decimal));
+		break;
+
 	case SQ_PS_PTEXT:
 		result = load_constant(code, sq_value_new(primary->text));
 		break;
@@ -815,12 +1244,16 @@ static unsigned compile_primary(struct sq_code *code, struct primary *primary) {
 static unsigned compile_unary(struct sq_code *code, struct unary_expression *unary) {
 	unsigned rhs, result;
 
-	rhs = compile_primary(code, unary->rhs);
+	if (unary->kind == SQ_PS_UPRIMARY) {
+		result = compile_primary(code, unary->rhs);
+		goto done;
+	}
+
+	rhs = compile_unary(code, unary->operand);
 
 	switch (unary->kind) {
 	case SQ_PS_UNEG: set_opcode(code, SQ_OC_NEG); break;
 	case SQ_PS_UNOT: set_opcode(code, SQ_OC_NOT); break;
-	case SQ_PS_UPRIMARY: result = rhs; goto done;
 	default: bug("unknown unary kind '%d'", unary->kind);
 	}
 
@@ -869,6 +1302,9 @@ static unsigned compile_mul(struct sq_code *code, struct mul_expression *mul) {
 	case SQ_PS_MMUL: set_opcode(code, SQ_OC_MUL); break;
 	case SQ_PS_MDIV: set_opcode(code, SQ_OC_DIV); break;
 	case SQ_PS_MMOD: set_opcode(code, SQ_OC_MOD); break;
+	case SQ_PS_MBAND: set_opcode(code, SQ_OC_BAND); break;
+	case SQ_PS_MSHL: set_opcode(code, SQ_OC_SHL); break;
+	case SQ_PS_MSHR: set_opcode(code, SQ_OC_SHR); break;
 	case SQ_PS_MPOW: result = lhs; goto done;
 	default: bug("unknown mul kind '%d'", mul->kind);
 	}
@@ -893,6 +1329,8 @@ static unsigned compile_add(struct sq_code *code, struct add_expression *add) {
 	switch (add->kind) {
 	case SQ_PS_AADD: set_opcode(code, SQ_OC_ADD); break;
 	case SQ_PS_ASUB: set_opcode(code, SQ_OC_SUB); break;
+	case SQ_PS_ABOR: set_opcode(code, SQ_OC_BOR); break;
+	case SQ_PS_ABXOR: set_opcode(code, SQ_OC_BXOR); break;
 	case SQ_PS_AMUL: result = lhs; goto done;
 	default: bug("unknown add kind '%d'", add->kind);
 	}
@@ -907,19 +1345,62 @@ done:
 	return result;
 }
 
+static unsigned compile_cmp(struct sq_code *code, struct cmp_expression *cmp);
+
+static bool is_relational(struct cmp_expression *cmp) {
+	return cmp->kind == SQ_PS_CLTH || cmp->kind == SQ_PS_CLEQ
+		|| cmp->kind == SQ_PS_CGTH || cmp->kind == SQ_PS_CGEQ;
+}
+
+// Compiles `lhs < cmp->rhs` (or whichever relational operator `cmp` is) into `target`. Chains such
+// as `a < b <= c` mean `a < b && b <= c`, except that `b` is only evaluated once.
+static void compile_relational(struct sq_code *code, unsigned lhs, struct cmp_expression *cmp, unsigned target) {
+	struct cmp_expression *next = cmp->rhs;
+	bool is_chained = is_relational(next);
+	unsigned rhs = is_chained ? compile_add(code, next->lhs) : compile_cmp(code, next);
+
+	switch (cmp->kind) {
+	case SQ_PS_CLTH: set_opcode(code, SQ_OC_LTH); break;
+	case SQ_PS_CLEQ: set_opcode(code, SQ_OC_LEQ); break;
+	case SQ_PS_CGTH: set_opcode(code, SQ_OC_GTH); break;
+	case SQ_PS_CGEQ: set_opcode(code, SQ_OC_GEQ); break;
+	default: bug("unknown relational kind '%d'", cmp->kind);
+	}
+
+	set_index(code, lhs);
+	set_index(code, rhs);
+	set_index(code, target);
+	free(cmp);
+
+	if (!is_chained)
+		return;
+
+	// the rest of the chain is only evaluated if this comparison held.
+	set_opcode(code, SQ_OC_JMP_FALSE);
+	set_index(code, target);
+	unsigned dst = code->codelen;
+	set_index(code, 65533);
+
+	compile_relational(code, rhs, next, target);
+	set_target_to_codelen(code, dst);
+}
+
 static unsigned compile_cmp(struct sq_code *code, struct cmp_expression *cmp) {
 	unsigned lhs, rhs, result;
 
 	lhs = compile_add(code, cmp->lhs);
+
+	if (is_relational(cmp)) {
+		compile_relational(code, lhs, cmp, result = next_local(code));
+		return result;
+	}
+
 	if (cmp->kind != SQ_PS_CADD)
 		rhs = compile_cmp(code, cmp->rhs);
 
 	switch (cmp->kind) {
-	case SQ_PS_CLTH: set_opcode(code, SQ_OC_LTH); break;
-	case SQ_PS_CLEQ: set_opcode(code, SQ_OC_LEQ); break;
-	case SQ_PS_CGTH: set_opcode(code, SQ_OC_GTH); break;
-	case SQ_PS_CGEQ: set_opcode(code, SQ_OC_GEQ); break;
 	case SQ_PS_CCMP: set_opcode(code, SQ_OC_CMP); break;
+	case SQ_PS_CRANGE: set_opcode(code, SQ_OC_INT); set_interrupt(code, SQ_INT_RANGE_NEW); break;
 	case SQ_PS_CADD: result = lhs; goto done;
 	default: bug("unknown cmp kind '%d'", cmp->kind);
 	}
@@ -973,8 +1454,7 @@ static unsigned compile_bool(struct sq_code *code, struct bool_expression *bool_
 	set_index(code, tmp);
 	set_index(code, target = next_local(code));
 
-	tmp = compile_bool(code, bool_->rhs);
-
+	// the rhs is only evaluated if the lhs doesn't already determine the result.
 	switch (bool_->kind) {
 	case SQ_PS_BAND: set_opcode(code, SQ_OC_JMP_FALSE); break;
 	case SQ_PS_BOR:  set_opcode(code, SQ_OC_JMP_TRUE); break;
@@ -986,6 +1466,8 @@ static unsigned compile_bool(struct sq_code *code, struct bool_expression *bool_
 	unsigned dst = code->codelen;
 	set_index(code, 65533);
 
+	tmp = compile_bool(code, bool_->rhs);
+
 	set_opcode(code, SQ_OC_MOV);
 	set_index(code, tmp);
 	set_index(code, target);
@@ -998,18 +1480,24 @@ done:
 }
 
 static unsigned compile_function_call_old(struct sq_code *code, struct function_call_old *fncall) {
-	unsigned args[fncall->arglen];
+	unsigned args[fncall->arglen], kwargs[fncall->kwargc + 1];
+	bool is_call = !fncall->kwargc; // builtins aren't calls, and keyword calls can't be tail calls.
 
 	for (unsigned i = 0; i < fncall->arglen; ++i)
 		args[i] = compile_expression(code, fncall->args[i]);
 
+	for (unsigned i = 0; i < fncall->kwargc; ++i)
+		kwargs[i] = compile_expression(code, fncall->kwargs[i].value);
+
 	if (fncall->func->field != NULL) {
 		set_opcode(code, SQ_OC_NOOP);
 		int dst;
 		unsigned var = load_variable_class(code, fncall->func, &dst);
-		set_opcode(code, SQ_OC_CALL);
+		code->last_call = code->codelen;
+		set_opcode(code, fncall->kwargc ? SQ_OC_KWCALL : SQ_OC_CALL);
 		set_index(code, var);
 		set_count(code, fncall->arglen + 1);
+		if (fncall->kwargc) set_count(code, fncall->kwargc);
 		set_index(code, dst);
 		goto arguments;
 	}
@@ -1018,8 +1506,11 @@ static unsigned compile_function_call_old(struct sq_code *code, struct function_
 	if (!strcmp(fncall->func->name, name_)) { \
 		if (fncall->arglen != argc_) \
 			die("exactly %d arg(s) are required for '%s'", argc_, name_); \
+		if (fncall->kwargc) \
+			die("keyword arguments cannot be passed to '%s'", name_); \
 		set_opcode(code, SQ_OC_INT); \
 		set_interrupt(code, int_); \
+		is_call = false; \
 		goto arguments; \
 	}
 
@@ -1037,11 +1528,24 @@ static unsigned compile_function_call_old(struct sq_code *code, struct function_
 	BUILTIN_FN("book",      SQ_INT_TOBOOK, 1);
 	BUILTIN_FN("codex",     SQ_INT_TOCODEX, 1);
 	BUILTIN_FN("genus",     SQ_INT_KINDOF, 1);
+	BUILTIN_FN("kind",      SQ_INT_KINDNAME, 1);
 
 	BUILTIN_FN("length",    SQ_INT_LENGTH, 1); // `fathoms` ? furlong
+	BUILTIN_FN("upper",     SQ_INT_UPPER, 1);
+	BUILTIN_FN("lower",     SQ_INT_LOWER, 1);
+	BUILTIN_FN("trim",      SQ_INT_TRIM, 1);
+	BUILTIN_FN("trim_start", SQ_INT_TRIM_START, 1);
+	BUILTIN_FN("trim_end",  SQ_INT_TRIM_END, 1);
+	BUILTIN_FN("split",     SQ_INT_SPLIT, 2);
+	BUILTIN_FN("join",      SQ_INT_JOIN, 2);
+	BUILTIN_FN("starts_with", SQ_INT_STARTS_WITH, 2);
+	BUILTIN_FN("ends_with", SQ_INT_ENDS_WITH, 2);
+	BUILTIN_FN("replace",   SQ_INT_REPLACE, 3);
 	BUILTIN_FN("substr",    SQ_INT_SUBSTR, 3);
 	BUILTIN_FN("insert",    SQ_INT_ARRAY_INSERT, 3);
 	BUILTIN_FN("delete",    SQ_INT_ARRAY_DELETE, 2); // `slay`?
+	BUILTIN_FN("contains",  SQ_INT_ARRAY_CONTAINS, 2);
+	BUILTIN_FN("index_of",  SQ_INT_ARRAY_INDEX_OF, 2);
 
 	BUILTIN_FN("gamble",    SQ_INT_RANDOM, 0);
 	BUILTIN_FN("roman",     SQ_INT_ROMAN, 1);
@@ -1060,19 +1564,96 @@ static unsigned compile_function_call_old(struct sq_code *code, struct function_
 
 	set_opcode(code, SQ_OC_NOOP);
 	unsigned var = load_variable_class(code, fncall->func, NULL);
-	set_opcode(code, SQ_OC_CALL);
+	code->last_call = code->codelen;
+	set_opcode(code, fncall->kwargc ? SQ_OC_KWCALL : SQ_OC_CALL);
 	set_index(code, var);
-	set_index(code, fncall->arglen);
+	set_count(code, fncall->arglen);
+	if (fncall->kwargc) set_count(code, fncall->kwargc);
 
 arguments:
 	for (unsigned i = 0; i < fncall->arglen; ++i)
 		set_index(code, args[i]);
 
+	for (unsigned i = 0; i < fncall->kwargc; ++i) {
+		set_index(code, new_constant(code, sq_value_new(sq_text_new(strdup(fncall->kwargs[i].name)))));
+		set_index(code, kwargs[i]);
+	}
+
 	unsigned result;
 
 	set_index(code, result = next_local(code));
+
+	if (is_call)
+		code->last_call_end = code->codelen;
+
+	return result;
+
+}
+
+static unsigned compile_ternary(struct sq_code *code, struct ternary *ternary) {
+	unsigned condition_index, value, result, iffalse_label, finished_label;
+
+	condition_index = compile_bool(code, ternary->cond);
+	set_opcode(code, SQ_OC_JMP_FALSE);
+	set_index(code, condition_index);
+	iffalse_label = code->codelen;
+	set_index(code, 0);
+
+	result = next_local(code);
+
+	value = compile_expression(code, ternary->iftrue);
+	set_opcode(code, SQ_OC_MOV);
+	set_index(code, value);
+	set_index(code, result);
+
+	set_opcode(code, SQ_OC_JMP);
+	finished_label = code->codelen;
+	set_index(code, 0);
+
+	set_target_to_codelen(code, iffalse_label);
+	value = compile_expression(code, ternary->iffalse);
+	set_opcode(code, SQ_OC_MOV);
+	set_index(code, value);
+	set_index(code, result);
+	set_target_to_codelen(code, finished_label);
+
+	free(ternary);
 	return result;
+}
+
+static unsigned compile_compound_assignment(struct sq_code *code, struct assignment *asgn) {
+	unsigned current, rhs, result;
+	struct variable_old *var = asgn->var;
+
+	current = load_identifier(code, var->name);
 
+	if (var->field) {
+		if (var->field->field)
+			die("only one layer deep for assignment supported rn");
+
+		set_opcode(code, SQ_OC_ILOAD);
+		set_index(code, current);
+		set_index(code, new_constant(code, sq_value_new(sq_text_new(strdup(var->field->name)))));
+		set_index(code, current = next_local(code));
+	}
+
+	rhs = compile_expression(code, asgn->expr);
+
+	switch (asgn->kind) {
+	case SQ_PS_ASGN_ADD: set_opcode(code, SQ_OC_ADD); break;
+	case SQ_PS_ASGN_SUB: set_opcode(code, SQ_OC_SUB); break;
+	case SQ_PS_ASGN_MUL: set_opcode(code, SQ_OC_MUL); break;
+	case SQ_PS_ASGN_DIV: set_opcode(code, SQ_OC_DIV); break;
+	case SQ_PS_ASGN_MOD: set_opcode(code, SQ_OC_MOD); break;
+	case SQ_PS_ASGN_POW: set_opcode(code, SQ_OC_POW); break;
+	default: bug("unknown assignment kind '%d'", asgn->kind);
+	}
+
+	set_index(code, current);
+	set_index(code, rhs);
+	set_index(code, result = next_local(code));
+
+	return result;
 }
 
 static unsigned compile_expression(struct sq_code *code, struct expression *expr) {
@@ -1098,9 +1679,14 @@ static unsigned compile_expression(struct sq_code *code, struct expression *expr
 	}
 
 	case SQ_PS_EASSIGN: {
-		index = compile_expression(code, expr->asgn->expr);
 		struct variable_old *var = expr->asgn->var;
-		variable = lookup_identifier(code, var->name);
+
+		if (expr->asgn->kind != SQ_PS_ASGN_PLAIN)
+			index = compile_compound_assignment(code, expr->asgn);
+		else
+			index = compile_expression(code, expr->asgn->expr);
+
+		variable = lookup_identifier(code, var->name, true);
 
 		if (!var->field) {
 			if (0 <= variable) {
@@ -1138,6 +1724,9 @@ static unsigned compile_expression(struct sq_code *code, struct expression *expr
 	case SQ_PS_EMATH:
 		return compile_bool(code, expr->math);
 
+	case SQ_PS_ETERNARY:
+		return compile_ternary(code, expr->ternary);
+
 	default:
 		bug("unknown expr kind '%d'", expr->kind);
 	}
@@ -1161,11 +1750,77 @@ done:
 	return index;
 }
 
+static unsigned declare_nigh_variable(struct sq_code *code, const char *name, bool sworn) {
+	// redeclaring a variable in the same block reuses it, otherwise we shadow whatever was outside.
+	for (unsigned i = code->vars.len; i--;) {
+		if (!strcmp(name, code->vars.ary[i].name)) {
+			if (code->vars.ary[i].depth != code->scope_depth)
+				break;
+
+			if (code->vars.ary[i].sworn)
+				die("cannot redeclare sworn variable '%s'", name);
+
+			code->vars.ary[i].sworn = sworn;
+			return code->vars.ary[i].index;
+		}
+	}
+
+	unsigned index = declare_local_variable(code, name, code->scope_depth);
+	code->vars.ary[code->vars.len - 1].sworn = sworn;
+	return index;
+}
+
+static void compile_destructuring_local(struct sq_code *code, struct scope_declaration *ldecl, unsigned book) {
+	unsigned count = ldecl->nnames + 1, rest = next_local(code);
+
+	// make sure `book` has the right amount of pages, and collect the leftovers for `*rest`.
+	unsigned count_index = load_constant(code, sq_value_new((sq_numeral) count));
+	unsigned has_rest_index = load_constant(code, sq_value_new((sq_veracity) (ldecl->rest != NULL)));
+	set_opcode(code, SQ_OC_INT);
+	set_interrupt(code, SQ_INT_ARRAY_UNPACK);
+	set_index(code, book);
+	set_index(code, count_index);
+	set_index(code, has_rest_index);
+	set_index(code, rest);
+
+	for (unsigned i = 0; i < count; ++i) {
+		char *name = i ? ldecl->names[i - 1] : ldecl->name;
+		unsigned page = load_constant(code, sq_value_new((sq_numeral) i + 1));
+
+		set_opcode(code, SQ_OC_INDEX);
+		set_index(code, book);
+		set_index(code, page);
+		set_index(code, declare_nigh_variable(code, name, ldecl->sworn));
+
+		if (i) free(name);
+	}
+
+	if (ldecl->rest != NULL) {
+		set_opcode(code, SQ_OC_MOV);
+		set_index(code, rest);
+		set_index(code, declare_nigh_variable(code, ldecl->rest, ldecl->sworn));
+		free(ldecl->rest);
+	}
+
+	free(ldecl->names);
+}
+
 static unsigned compile_local(struct sq_code *code, struct scope_declaration *ldecl) {
-	unsigned index = new_local_variable(code, ldecl->name);
+	unsigned index, result = 0;
+
+	// compile the value first, so `nigh x = x` in a block refers to the outer `x`.
+	if (ldecl->value != NULL)
+		result = compile_expression(code, ldecl->value);
+
+	if (ldecl->names != NULL) {
+		compile_destructuring_local(code, ldecl, result);
+		free(ldecl);
+		return result;
+	}
+
+	index = declare_nigh_variable(code, ldecl->name, ldecl->sworn);
 
 	if (ldecl->value != NULL) {
-		unsigned result = compile_expression(code, ldecl->value);
 		set_opcode(code, SQ_OC_MOV);
 		set_index(code, result);
 		set_index(code, index);
@@ -1175,127 +1830,127 @@ static unsigned compile_local(struct sq_code *code, struct scope_declaration *ld
 	return index;
 }
 
-static void create_label_statement(struct sq_code *code, char *label, bool in_mem) {
-	unsigned len = code->labels.len++;
-	unsigned i;
+/* layout:
+COMEFROM <amount of whences> <index after the whences> <whence>... <padding>...
+
+When no `whence`s refer to the label, it's a no-op. Otherwise, execution jumps
+to just after the `whence`, and forks for every additional `whence`. Labels are
+compiled before `whence`s are known, so there's always room for MAX_COMEFROMS.
+*/
+static void emit_comefrom(struct sq_code *code, struct label *lbl) {
+	unsigned amount = 0, end;
 
-	// havent found the label, add it. (note we increase len here)
-	if (len == code->labels.cap)
-		code->labels.ary = xrealloc(code->labels.ary, sizeof_array(struct label, code->labels.cap *= 2));
+	set_opcode(code, SQ_OC_COMEFROM);
+	lbl->position = code->codelen;
+	set_count(code, 0); // updated below
+	set_index(code, end = code->codelen + 1 + MAX_COMEFROMS);
 
-	code->labels.ary[len].name = label;
-	if (in_mem) {
-		set_opcode(code, SQ_OC_COMEFROM);
-		code->labels.ary[len].length = (int *) &code->bytecode[code->codelen];
 #ifdef SQ_WHENCE_CONTINUES_ON // ie continues onwards in addition to jumping
-		set_index(code, i = 1);
-		set_index(code, code->codelen + MAX_COMEFROMS); 
-#else
-		set_index(code, i = 0);
+	set_index(code, end);
+	++amount;
 #endif
-	} else {
-		code->labels.ary[len].length = NULL;
-		i = 0;
-	}
 
-	for (; i < MAX_COMEFROMS; ++i) {
-		code->labels.ary[len].indices[i] = -1;
+	for (unsigned i = 0; i < MAX_COMEFROMS; ++i) {
+		if (lbl->indices[i] == -1)
+			continue;
+
+		if (amount == MAX_COMEFROMS)
+			die("max amount of 'whence's encountered.");
 
-		if (in_mem) set_index(code, -1);
+		set_index(code, lbl->indices[i]);
+		++amount;
 	}
+
+	while (code->codelen < end)
+		set_index(code, -1);
+
+	code->bytecode[lbl->position].count = amount;
 }
 
-static void compile_label_statement(struct sq_code *code, char *label) {
-	struct label *lbl;
-	unsigned j;
-	for (unsigned i = 0; i < code->labels.len; ++i) {
-		// we've found a destination, assign to that.
-		if (!strcmp((lbl=&code->labels.ary[i])->name, label)) {
-			if (lbl->length != NULL)
-				die("cannot redefine '%s'", label);
-			free(label);
+static struct label *lookup_label(struct sq_code *code, const char *name) {
+	for (unsigned i = 0; i < code->labels.len; ++i)
+		if (!strcmp(code->labels.ary[i].name, name))
+			return &code->labels.ary[i];
 
-			set_opcode(code, SQ_OC_COMEFROM);
-			lbl->length = (int *) &code->bytecode[code->codelen];
+	return NULL;
+}
 
-#ifdef SQ_WHENCE_CONTINUES_ON // ie continues onwards in addition to jumping
-			set_index(code, j = 1);
-			set_index(code, code->codelen + MAX_COMEFROMS); 
-#else
-			set_index(code, j = 0);
-#endif
+static struct label *create_label(struct sq_code *code, char *name) {
+	RESIZE(labels.cap, labels.len, labels.ary, struct label);
 
-			for (; j < MAX_COMEFROMS; ++j) {
-				if (lbl->indices[j] != -1) ++*lbl->length;
-				set_index(code, lbl->indices[j]);
-			}
+	struct label *lbl = &code->labels.ary[code->labels.len++];
+	lbl->name = name;
+	lbl->position = -1;
 
-			return;
-		}
-	}
+	for (unsigned i = 0; i < MAX_COMEFROMS; ++i)
+		lbl->indices[i] = -1;
 
-	// haven't found it, need to set it.
-	create_label_statement(code, label, true);
+	return lbl;
 }
 
-static void compile_comefrom_statement(struct sq_code *code, char *label, bool thence) {
-	(void) thence;
-	unsigned i, j;
+static void compile_label_statement(struct sq_code *code, char *label) {
 	struct label *lbl;
 
-	// check to see if the label statement exists.
-	for (i = 0; i < code->labels.len; ++i) {
-		if (!strcmp(code->labels.ary[i].name, label)) {
-			lbl = &code->labels.ary[i];
-
-			// if the label already exists, make it go here.
-			free(label);
-			if (lbl->length != NULL) goto already_exists;
+	if ((lbl = lookup_label(code, label))) {
+		if (lbl->position != -1)
+			die("cannot redefine '%s'", label);
 
-			for (j = 0; j < MAX_COMEFROMS; ++j)
-				if (lbl->indices[j] == -1) {
-					goto set_existing;
-				}
-
-			die("max amount of 'whence's encountered.");
-		}
+		free(label);
+	} else {
+		lbl = create_label(code, label);
 	}
 
-	j = 0;
-
-	// it doesn't, create it.
-	create_label_statement(code, label, false);
+	emit_comefrom(code, lbl);
+}
 
-set_existing:
+static void compile_comefrom_statement(struct sq_code *code, char *label, bool thence) {
+	(void) thence;
+	struct label *lbl;
 
-	code->labels.ary[i].indices[j] = code->codelen;
-	return;
+	if ((lbl = lookup_label(code, label)))
+		free(label);
+	else
+		lbl = create_label(code, label);
+
+	// the label's not been compiled yet, so have it jump here when it is.
+	if (lbl->position == -1) {
+		for (unsigned i = 0; i < MAX_COMEFROMS; ++i) {
+			if (lbl->indices[i] == -1) {
+				lbl->indices[i] = code->codelen;
+				return;
+			}
+		}
 
-already_exists:
+		die("max amount of 'whence's encountered.");
+	}
 
-	if (*lbl->length == MAX_COMEFROMS) die("max amount of 'whence's encountered.");
+	unsigned amount = code->bytecode[lbl->position].count;
+	if (amount == MAX_COMEFROMS) die("max amount of 'whence's encountered.");
 
-	unsigned length = ++*lbl->length;
-	lbl->length[length] = code->codelen;
+	code->bytecode[lbl->position + 2 + amount].index = code->codelen;
+	code->bytecode[lbl->position].count = amount + 1;
 }
 
 static void compile_trycatch_statement(struct sq_code *code, struct trycatch_statement *tc) {
-	unsigned *catchblock, *noerror, exception;
+	unsigned catch_label, noerror_label;
 
+	// note we use labels, not pointers, as `bytecode` may be reallocated.
 	set_opcode(code, SQ_OC_TRYCATCH);
-	catchblock = CURRENT_INDEX_PTR(code);
+	catch_label = code->codelen;
 	set_index(code, -1);
-	set_index(code, exception = new_local_variable(code, tc->exception));
+	set_index(code, new_local_variable(code, tc->exception));
 
+	++code->trycatch_depth;
 	compile_statements(code, tc->try);
+	--code->trycatch_depth;
 	set_opcode(code, SQ_OC_POPTRYCATCH);
 	set_opcode(code, SQ_OC_JMP);
-	noerror = CURRENT_INDEX_PTR(code);
+	noerror_label = code->codelen;
 	set_index(code, -1);
 
-	*catchblock = code->codelen;
+	set_target_to_codelen(code, catch_label);
 	compile_statements(code, tc->catch);
-	*noerror = code->codelen;
+	set_target_to_codelen(code, noerror_label);
 
 	// free(tc->exception);
 	free(tc);
@@ -1307,7 +1962,239 @@ static void compile_throw_statement(struct sq_code *code, struct expression *thr
 	set_index(code, dst);
 }
 
+static void compile_assert_statement(struct sq_code *code, struct assert_statement *challenge) {
+	unsigned condition_index, finished_label, message;
+
+	condition_index = compile_expression(code, challenge->cond);
+	set_opcode(code, SQ_OC_JMP_TRUE);
+	set_index(code, condition_index);
+	finished_label = code->codelen;
+	set_index(code, 0);
+
+	if (challenge->message) {
+		unsigned prefix = load_constant(code, sq_value_new(sq_text_new(strdup("challenge failed: "))));
+		unsigned description = compile_expression(code, challenge->message);
+
+		set_opcode(code, SQ_OC_ADD);
+		set_index(code, prefix);
+		set_index(code, description);
+		set_index(code, message = next_local(code));
+	} else {
+		message = load_constant(code, sq_value_new(sq_text_new(strdup("challenge failed"))));
+	}
+
+	set_opcode(code, SQ_OC_THROW);
+	set_index(code, message);
+	set_target_to_codelen(code, finished_label);
+
+	free(challenge);
+}
+
+// What an expression without side effects results in. Expressions whose
+// results are never used (e.g. `III + IV;`) don't need to be compiled at all if
+// they're pure, but operators can throw (or call changes) depending on the kinds
+// of their operands, so we need to keep track of what those are. Numeral
+// arithmetic throws if it overflows, so it's only pure if it's entirely made of
+// numeral literals, and working it out here doesn't overflow.
+enum purity { IMPURE, PURE_NUMERAL, PURE_TEXT, PURE_VERACITY, PURE_OTHER };
+
+static enum purity expression_purity(struct expression *expr);
+
+static bool fits_in_numeral(sq_numeral numeral) {
+	return (INT64_MIN >> SQ_VSHIFT) <= numeral && numeral <= (INT64_MAX >> SQ_VSHIFT);
+}
+
+static bool constant_add(struct add_expression *add, sq_numeral *result);
+
+// Works out the value of `unary` into `result` if it's a numeral literal (or the negation of one),
+// returning whether it was.
+static bool constant_unary(struct unary_expression *unary, sq_numeral *result) {
+	if (unary->kind == SQ_PS_UNEG)
+		return constant_unary(unary->operand, result) && fits_in_numeral(*result = -*result);
+
+	if (unary->kind != SQ_PS_UPRIMARY)
+		return false;
+
+	struct primary *primary = unary->rhs;
+
+	if (primary->kind == SQ_PS_PNUMERAL) {
+		*result = primary->numeral;
+		return true;
+	}
+
+	// parenthesized arithmetic, eg `(I + II)`.
+	return primary->kind == SQ_PS_PPAREN
+		&& primary->expr->kind == SQ_PS_EMATH
+		&& primary->expr->math->kind == SQ_PS_BEQL
+		&& primary->expr->math->lhs->kind == SQ_PS_ECMP
+		&& primary->expr->math->lhs->lhs->kind == SQ_PS_CADD
+		&& constant_add(primary->expr->math->lhs->lhs->lhs, result);
+}
+
+// Like `constant_unary`, but for `*`. Just like when it's compiled, `mul->rhs` is evaluated on its
+// own first, so that the overflow checks here match the ones at runtime.
+static bool constant_mul(struct mul_expression *mul, sq_numeral *result) {
+	sq_numeral rhs;
+
+	if (mul->lhs->kind != SQ_PS_PUNARY || !constant_unary(mul->lhs->lhs, result))
+		return false;
+
+	switch (mul->kind) {
+	case SQ_PS_MPOW:
+		return true;
+
+	case SQ_PS_MMUL:
+		return constant_mul(mul->rhs, &rhs)
+			&& !__builtin_mul_overflow(*result, rhs, result) && fits_in_numeral(*result);
+
+	default:
+		return false;
+	}
+}
+
+// Like `constant_mul`, but for `+` and `-`.
+static bool constant_add(struct add_expression *add, sq_numeral *result) {
+	sq_numeral rhs;
+
+	if (!constant_mul(add->lhs, result))
+		return false;
+
+	switch (add->kind) {
+	case SQ_PS_AMUL:
+		return true;
+
+	case SQ_PS_AADD:
+		return constant_add(add->rhs, &rhs)
+			&& !__builtin_add_overflow(*result, rhs, result) && fits_in_numeral(*result);
+
+	case SQ_PS_ASUB:
+		return constant_add(add->rhs, &rhs)
+			&& !__builtin_sub_overflow(*result, rhs, result) && fits_in_numeral(*result);
+
+	default:
+		return false;
+	}
+}
+
+static enum purity primary_purity(struct primary *primary) {
+	switch (primary->kind) {
+	case SQ_PS_PPAREN: return expression_purity(primary->expr);
+	case SQ_PS_PNUMERAL:
+	case SQ_PS_PDECIMAL: return PURE_NUMERAL;
+	case SQ_PS_PTEXT: return PURE_TEXT;
+	case SQ_PS_PVERACITY: return PURE_VERACITY;
+	case SQ_PS_PNI: return PURE_OTHER;
+
+	case SQ_PS_PBOOK:
+		for (unsigned i = 0; i < primary->book->npages; ++i)
+			if (expression_purity(primary->book->pages[i]) == IMPURE)
+				return IMPURE;
+		return PURE_OTHER;
+
+	case SQ_PS_PCODEX:
+		for (unsigned i = 0; i < primary->dict->neles; ++i)
+			if (expression_purity(primary->dict->keys[i]) == IMPURE
+				|| expression_purity(primary->dict->vals[i]) == IMPURE)
+				return IMPURE;
+		return PURE_OTHER;
+
+	// variables are impure so that unknown ones are still reported.
+	default:
+		return IMPURE;
+	}
+}
+
+static enum purity unary_purity(struct unary_expression *unary) {
+	if (unary->kind == SQ_PS_UPRIMARY)
+		return primary_purity(unary->rhs);
+
+	enum purity rhs = unary_purity(unary->operand);
+
+	sq_numeral folded;
+
+	switch (unary->kind) {
+	case SQ_PS_UNOT: return rhs == IMPURE ? IMPURE : PURE_VERACITY;
+	case SQ_PS_UNEG: return constant_unary(unary, &folded) ? PURE_NUMERAL : IMPURE;
+	default: bug("unknown unary kind '%d'", unary->kind);
+	}
+}
+
+static enum purity pow_purity(struct pow_expression *pow) {
+	// exponentiation can fail, eg with negative exponents.
+	return pow->kind == SQ_PS_PUNARY ? unary_purity(pow->lhs) : IMPURE;
+}
+
+static enum purity mul_purity(struct mul_expression *mul) {
+	sq_numeral folded;
+
+	if (mul->kind == SQ_PS_MPOW)
+		return pow_purity(mul->lhs);
+
+	return constant_mul(mul, &folded) ? PURE_NUMERAL : IMPURE;
+}
+
+static enum purity add_purity(struct add_expression *add) {
+	enum purity lhs = mul_purity(add->lhs), rhs;
+	sq_numeral folded;
+
+	if (add->kind == SQ_PS_AMUL)
+		return lhs;
+
+	if (constant_add(add, &folded))
+		return PURE_NUMERAL;
+
+	if ((rhs = add_purity(add->rhs)) == IMPURE)
+		return IMPURE;
+
+	if (add->kind == SQ_PS_AADD && lhs == PURE_TEXT && rhs != PURE_OTHER)
+		return PURE_TEXT;
+
+	return IMPURE;
+}
+
+static enum purity cmp_purity(struct cmp_expression *cmp) {
+	enum purity lhs = add_purity(cmp->lhs);
+
+	if (cmp->kind == SQ_PS_CADD)
+		return lhs;
+
+	if (cmp->kind == SQ_PS_CRANGE)
+		return lhs == PURE_NUMERAL && cmp_purity(cmp->rhs) == PURE_NUMERAL ? PURE_OTHER : IMPURE;
+
+	if ((lhs != PURE_NUMERAL && lhs != PURE_TEXT) || lhs != cmp_purity(cmp->rhs))
+		return IMPURE;
+
+	return cmp->kind == SQ_PS_CCMP ? PURE_NUMERAL : PURE_VERACITY;
+}
+
+static enum purity eql_purity(struct eql_expression *eql) {
+	enum purity lhs = cmp_purity(eql->lhs);
+
+	switch (eql->kind) {
+	case SQ_PS_ECMP: return lhs;
+	case SQ_PS_EEQL:
+	case SQ_PS_ENEQ: return lhs != IMPURE && eql_purity(eql->rhs) != IMPURE ? PURE_VERACITY : IMPURE;
+	default: return IMPURE;
+	}
+}
+
+static enum purity bool_purity(struct bool_expression *bool_) {
+	enum purity lhs = eql_purity(bool_->lhs);
+
+	if (bool_->kind == SQ_PS_BEQL)
+		return lhs;
+
+	return lhs != IMPURE && bool_purity(bool_->rhs) != IMPURE ? PURE_OTHER : IMPURE;
+}
+
+static enum purity expression_purity(struct expression *expr) {
+	return expr->kind == SQ_PS_EMATH ? bool_purity(expr->math) : IMPURE;
+}
+
 static void compile_statement(struct sq_code *code, struct statement *stmt) {
+	// any temporaries from this statement are dead once it's done.
+	unsigned temps_used = code->temps.used;
+
 	switch (stmt->kind) {
 	case SQ_PS_SGLOBAL: compile_global(code, stmt->gdecl); break;
 	case SQ_PS_SLOCAL: compile_local(code, stmt->ldecl); break;
@@ -1316,26 +2203,41 @@ static void compile_statement(struct sq_code *code, struct statement *stmt) {
 	case SQ_PS_SJOURNEY: compile_journey_declaration(stmt->jdecl); break;
 	case SQ_PS_SIF: compile_if_statement(code, stmt->ifstmt); break;
 	case SQ_PS_SWHILE: compile_while_statement(code, stmt->wstmt); break;
+	case SQ_PS_SFOR: compile_for_statement(code, stmt->forstmt); break;
 	case SQ_PS_SLABEL: compile_label_statement(code, stmt->label); break;
+	case SQ_PS_SLOOPCONTROL: compile_loop_control_statement(code, stmt->loopctl); break;
 	case SQ_PS_SCOMEFROM: compile_comefrom_statement(code, stmt->comefrom, false); break;
 	case SQ_PS_STHENCE: compile_comefrom_statement(code, stmt->thence, true); break;
 	case SQ_PS_SRETURN: compile_return_statement(code, stmt->rstmt); break;
 	case SQ_PS_STRYCATCH: compile_trycatch_statement(code, stmt->tcstmt); break;
 	case SQ_PS_STHROW: compile_throw_statement(code, stmt->throwstmt); break;
+	case SQ_PS_SASSERT: compile_assert_statement(code, stmt->assertstmt); break;
 	case SQ_PS_SSWITCH: compile_switch_statement(code, stmt->sw_stmt); break;
-	case SQ_PS_SEXPR: compile_expression(code, stmt->expr); break;
+	case SQ_PS_SEXPR:
+		// the result's unused, so there's no need to compile it if it has no side effects.
+		if (!sq_program_optimize || expression_purity(stmt->expr) == IMPURE)
+			compile_expression(code, stmt->expr);
+		break;
 	}
+
+	code->temps.used = temps_used;
 }
 
 static void compile_statements(struct sq_code *code, struct statements *stmts) {
+	begin_scope(code);
+
 	for (unsigned i = 0; i < stmts->len; ++i)
 		compile_statement(code, stmts->stmts[i]);
+
+	end_scope(code);
 }
 
 static void compile_journey_pattern(
 	struct sq_journey_pattern *pattern,
 	struct journey_pattern *jp,
-	bool is_method
+	bool is_method,
+	struct sq_code *enclosing,
+	struct captures *captures
 ) {
 	(void) is_method;
 	pattern->pargc = jp->pargc;
@@ -1355,21 +2257,49 @@ static void compile_journey_pattern(
 	code.consts.len = 0;
 	code.consts.ary = xmalloc(sizeof_array(sq_value, code.consts.cap));
 
+	code.constmap.len = 0;
+	code.constmap.cap = 64; // must be a power of two
+	code.constmap.buckets = xmalloc(sizeof_array(int, code.constmap.cap));
+	memset(code.constmap.buckets, -1, sizeof_array(int, code.constmap.cap));
+
 	code.vars.len = 0;
 	code.vars.cap = SQ_JOURNEY_MAX_ARGC * 2 + 2; // *2 for both positional and kw, then +2 for splat and splatsplat
 	code.vars.ary = xmalloc(sizeof_array(struct local, code.vars.cap));
+	code.scope_depth = 0;
+
+	code.enclosing = enclosing;
+	code.captures = captures;
+	code.capture_locals.len = 0;
+	code.capture_locals.cap = 4;
+	code.capture_locals.ary = xmalloc(sizeof_array(struct sq_journey_capture, code.capture_locals.cap));
 
 	code.labels.len = 0;
 	code.labels.cap = 4;
 	code.labels.ary = xmalloc(sizeof_array(struct label, code.labels.cap));
 
+	code.temps.len = code.temps.used = 0;
+	code.temps.cap = 16;
+	code.temps.ary = xmalloc(sizeof_array(unsigned, code.temps.cap));
+
+	code.loops.len = 0;
+	code.loops.cap = 4;
+	code.loops.ary = xmalloc(sizeof_array(struct loop, code.loops.cap));
+	code.trycatch_depth = 0;
+	code.last_call_end = 0;
+	code.can_tail_call = jp->return_genus == NULL;
+
 	unsigned local_index = 0;
 
-	for (unsigned i = 0; i < pattern->pargc; ++i, ++code.vars.len) {
+	for (unsigned i = 0; i < pattern->pargc; ++i) {
 		pattern->pargv[i].name = jp->pargv[i].name;
 
+		// the parameter is declared before its default and genus are compiled, so that any
+		// variables they declare don't clobber it.
 		code.vars.ary[code.vars.len].name = strdup(jp->pargv[i].name);
-		code.vars.ary[code.vars.len].index = local_index++;
+		code.vars.ary[code.vars.len].depth = 0;
+		code.vars.ary[code.vars.len].assigned = true;
+		code.vars.ary[code.vars.len].sworn = false;
+		code.vars.ary[code.vars.len++].index = local_index++;
 
 		if (jp->pargv[i].default_ == NULL) { 
 			pattern->pargv[i].default_start = -1;
@@ -1392,22 +2322,45 @@ static void compile_journey_pattern(
 
 	if (jp->splat) {
 		code.vars.ary[code.vars.len].name = strdup(jp->splat);
+		code.vars.ary[code.vars.len].depth = 0;
+		code.vars.ary[code.vars.len].assigned = true;
+		code.vars.ary[code.vars.len].sworn = false;
 		code.vars.ary[code.vars.len++].index = local_index++;
 	}
 
-	for (unsigned i = 0; i < pattern->kwargc; ++i, ++code.vars.len) {
+	for (unsigned i = 0; i < pattern->kwargc; ++i) {
 		pattern->kwargv[i].name = jp->kwargv[i].name;
 
-		code.vars.ary[code.vars.len].name = strdup(jp->pargv[i].name);
-		code.vars.ary[code.vars.len].index = local_index++;
+		code.vars.ary[code.vars.len].name = strdup(jp->kwargv[i].name);
+		code.vars.ary[code.vars.len].depth = 0;
+		code.vars.ary[code.vars.len].assigned = true;
+		code.vars.ary[code.vars.len].sworn = false;
+		code.vars.ary[code.vars.len++].index = local_index++;
 
-		assert(jp->kwargv[i].genus == NULL); // todo
-		assert(jp->kwargv[i].default_ == NULL); // todo
-		// todo: use `i` when compiling default.
+		if (jp->kwargv[i].default_ == NULL) {
+			pattern->kwargv[i].default_start = -1;
+		} else {
+			pattern->kwargv[i].default_start = code.codelen;
+			unsigned dst = compile_expression(&code, jp->kwargv[i].default_);
+			set_opcode(&code, SQ_OC_RETURN);
+			set_index(&code, dst);
+		}
+
+		if (jp->kwargv[i].genus == NULL) {
+			pattern->kwargv[i].genus_start = -1;
+		} else {
+			pattern->kwargv[i].genus_start = code.codelen;
+			unsigned dst = compile_primary(&code, jp->kwargv[i].genus);
+			set_opcode(&code, SQ_OC_RETURN);
+			set_index(&code, dst);
+		}
 	}
 
 	if (jp->splatsplat) {
 		code.vars.ary[code.vars.len].name = strdup(jp->splatsplat);
+		code.vars.ary[code.vars.len].depth = 0;
+		code.vars.ary[code.vars.len].assigned = true;
+		code.vars.ary[code.vars.len].sworn = false;
 		code.vars.ary[code.vars.len++].index = local_index++;
 	}
 
@@ -1420,23 +2373,50 @@ static void compile_journey_pattern(
 		pattern->condition_start = -1;
 	}
 
+	if (jp->return_genus) {
+		pattern->return_genus_start = code.codelen;
+		unsigned dst = compile_primary(&code, jp->return_genus);
+		set_opcode(&code, SQ_OC_RETURN);
+		set_index(&code, dst);
+	} else {
+		pattern->return_genus_start = -1;
+	}
+
 	assert(jp->body != NULL);
 
 	pattern->start_index = code.codelen;
 	compile_statements(&code, jp->body);
 
+	for (unsigned i = 0; i < code.vars.len; ++i)
+		if (!code.vars.ary[i].assigned)
+			die("undefined variable '%s'", code.vars.ary[i].name);
+
+	pattern->ncaptures = code.capture_locals.len;
+	pattern->captures = code.capture_locals.ary;
 	pattern->code.nlocals = code.nlocals;
 	pattern->code.nconsts = code.consts.len;
 	pattern->code.codelen = code.codelen;
 	pattern->code.consts = code.consts.ary;
 	pattern->code.bytecode = code.bytecode;
 
+	if (sq_program_optimize)
+		sq_journey_pattern_optimize(pattern);
+
+#ifdef SQ_LOG
+	sq_codeblock_disassemble(stdout, &pattern->code, NULL);
+#endif /* SQ_LOG */
+
 	// todo: free everything made by `code`.
 
 	return;
 }
 
-static struct sq_journey *compile_journey(struct journey_declaration *jd, bool is_method) {
+static struct sq_journey *compile_journey_within(
+	struct journey_declaration *jd,
+	bool is_method,
+	struct sq_code *enclosing,
+	struct captures *captures
+) {
 	struct sq_journey *journey = xmalloc(sizeof(struct sq_journey));
 
 	journey->name = jd->name;
@@ -1444,14 +2424,30 @@ static struct sq_journey *compile_journey(struct journey_declaration *jd, bool i
 	journey->npatterns = jd->npatterns;
 	journey->program = program;
 	journey->is_method = is_method;
+	journey->ncaptures = 0;
+	journey->captures = NULL;
 	journey->patterns = xmalloc(sizeof_array(struct sq_journey_pattern, jd->npatterns));
 
 	for (unsigned i = 0; i < jd->npatterns; ++i)
-		compile_journey_pattern(&journey->patterns[i], &jd->patterns[i], is_method);
+		compile_journey_pattern(&journey->patterns[i], &jd->patterns[i], is_method, enclosing, captures);
 
 	return journey;
 }
 
+static struct sq_journey *compile_journey(struct journey_declaration *jd, bool is_method) {
+	return compile_journey_within(jd, is_method, NULL, NULL);
+}
+
+// Journey expressions can use the variables of the code they're within; each one that's used is
+// added to `captures`, and is copied into the journey when the expression is run.
+static struct sq_journey *compile_nested_journey(
+	struct journey_declaration *jd,
+	struct sq_code *enclosing,
+	struct captures *captures
+) {
+	return compile_journey_within(jd, false, enclosing, captures);
+}
+
 static void setup_globals(void) {
 	globals.len = 0;
 	globals.ary = xmalloc(sizeof_array(struct local, globals.cap = 16));
@@ -1462,6 +2458,9 @@ static void setup_globals(void) {
 	globals.ary[globals.len  ].name = strdup("Numeral");
 	globals.ary[globals.len++].value = sq_value_new(sq_text_new(strdup("Numeral")));
 
+	globals.ary[globals.len  ].name = strdup("Decimal");
+	globals.ary[globals.len++].value = sq_value_new(sq_text_new(strdup("Decimal")));
+
 	globals.ary[globals.len  ].name = strdup("Text");
 	globals.ary[globals.len++].value = sq_value_new(sq_text_new(strdup("Text")));
 
@@ -1485,6 +2484,44 @@ static void setup_globals(void) {
 
 	globals.ary[globals.len  ].name = strdup("Codex");
 	globals.ary[globals.len++].value = sq_value_new(sq_text_new(strdup("Codex")));
+
+	globals.ary[globals.len  ].name = strdup("Range");
+	globals.ary[globals.len++].value = sq_value_new(sq_text_new(strdup("Range")));
+
+	for (unsigned i = 0; i < builtins.len; ++i) {
+		struct sq_other *other = sq_value_as_other(builtins.ary[i]);
+		declare_global_variable(other->builtin_journey.name, sq_value_clone(builtins.ary[i]));
+	}
+}
+
+void sq_program_register_builtin(const char *name, unsigned nargs, sq_value (*func)(struct sq_args args)) {
+	struct sq_other *other = xmalloc(sizeof(struct sq_other));
+
+	other->refcount = 1;
+	other->kind = SQ_OK_BUILTIN_JOURNEY;
+	other->builtin_journey.name = strdup(name);
+	other->builtin_journey.nargs = nargs;
+	other->builtin_journey.func = func;
+
+	if (builtins.len == builtins.cap)
+		builtins.ary = xrealloc(builtins.ary, sizeof_array(sq_value, builtins.cap = builtins.cap * 2 + 4));
+
+	builtins.ary[builtins.len++] = sq_value_new(other);
+}
+
+// Declares every journey, form, and `renowned` at the top level of the file up front, so they can
+// be referenced before they're defined. Anything else that isn't a local is an undefined variable.
+static void declare_toplevel_globals(struct statements *stmts) {
+	for (unsigned i = 0; i < stmts->len; ++i) {
+		struct statement *stmt = stmts->stmts[i];
+
+		switch (stmt->kind) {
+		case SQ_PS_SJOURNEY: new_global(stmt->jdecl->name); break;
+		case SQ_PS_SCLASS: new_global(stmt->cdecl->name); break;
+		case SQ_PS_SGLOBAL: new_global(stmt->gdecl->name); break;
+		default: break;
+		}
+	}
 }
 
 void sq_program_compile(struct sq_program *program_, const char *stream) {
@@ -1493,6 +2530,11 @@ void sq_program_compile(struct sq_program *program_, const char *stream) {
 	program = program_;
 	program->nglobals = 1;
 	program->globals = NULL;
+	program->global_names = NULL;
+	program->output = stdout;
+	program->input = stdin;
+	program->max_steps = 0;
+	program->steps = 0;
 
 	struct journey_declaration maindecl = {
 		.name = strdup("main"),
@@ -1509,11 +2551,16 @@ void sq_program_compile(struct sq_program *program_, const char *stream) {
 		}
 	};
 
+	declare_toplevel_globals(maindecl.patterns[0].body);
 	program->main = compile_journey(&maindecl, false);
 
 	program->nglobals = globals.len;
 	program->globals = xmalloc(sizeof_array(sq_value , globals.len));
 
-	for (unsigned i = 0; i < program->nglobals; ++i)
+	program->global_names = xmalloc(sizeof_array(char *, globals.len));
+
+	for (unsigned i = 0; i < program->nglobals; ++i) {
 		program->globals[i] = globals.ary[i].value;
+		program->global_names[i] = strdup(globals.ary[i].name);
+	}
 }
@@ -100,16 +100,12 @@ static void parse_henceforth_literal(struct macro_variable *var) {
 
 	while (true) {
 		switch ((tokens[len++] = sq_next_token()).kind) {
-		case SQ_TK_LTH:
-			if (*sq_stream != '<') break;
-			++sq_stream;
+		case SQ_TK_SHL:
 			--len;
 			is_verbatim = true;
 			continue;
 
-		case SQ_TK_GTH:
-			if (*sq_stream != '>') break;
-			++sq_stream;
+		case SQ_TK_SHR:
 			--len;
 			is_verbatim = false;
 			continue;
@@ -120,7 +116,7 @@ static void parse_henceforth_literal(struct macro_variable *var) {
 			goto done;
 
 		case SQ_TK_UNDEFINED:
-			die("unterminated macro");
+			die(SQ_PE_INVALID_MACRO, "unterminated macro");
 
 		default:
 			break;
@@ -148,8 +144,8 @@ static void parse_henceforth_function(struct macro_variable *var) {
 	while (true) {
 		strip_whitespace(true);
 		if ((c = *sq_stream++) == ')') break;
-		if (c != '$') die("expected '$' or ')'");
-		if (arglen == MAX_ARGLEN) die("too many arguments");
+		if (c != '$') die(SQ_PE_INVALID_MACRO, "expected '$' or ')'");
+		if (arglen == MAX_ARGLEN) die(SQ_PE_LIMIT_EXCEEDED, "too many arguments");
 
 		args[arglen++] = parse_identifier().identifier;
 		strip_whitespace(true);
@@ -157,7 +153,7 @@ static void parse_henceforth_function(struct macro_variable *var) {
 	}
 
 	if (sq_next_token().kind != SQ_TK_ASSIGN)
-		die("expected '=' after macro function declaration");
+		die(SQ_PE_INVALID_MACRO, "expected '=' after macro function declaration");
 
 	parse_henceforth_literal(var);
 	var->args = xrealloc(args, sizeof_array(char *, arglen));
@@ -169,7 +165,7 @@ static char *parse_macro_identifier_name(void) {
 	strip_whitespace(true);
 
 	if (*sq_stream++ != '$')
-		die("expected a macro identifier");
+		die(SQ_PE_INVALID_MACRO, "expected a macro identifier");
 
 	return parse_identifier().identifier;
 }
@@ -202,7 +198,7 @@ found_token:;
 	} else if (token.kind == SQ_TK_LPAREN) {
 		parse_henceforth_function(var);
 	} else {
-		die("unknown token after @henceforth");
+		die(SQ_PE_INVALID_MACRO, "unknown token after @henceforth");
 	}
 }
 
@@ -253,7 +249,7 @@ static void parse_whereupon(void) {
 		}
 
 		if ((token = sq_next_token()).kind == SQ_TK_UNDEFINED)
-			die("`@nowhere` found nowhere.");
+			die(SQ_PE_INVALID_MACRO, "`@nowhere` found nowhere.");
 
 		if (!is_defined) continue;
 
@@ -296,7 +292,7 @@ static bool should_compile(char *filename) {
 
 static void parse_transcribe(void) {
 	strip_whitespace(true);
-	if (*sq_stream != '\'' && *sq_stream != '\"') die("can only compile strings");
+	if (*sq_stream != '\'' && *sq_stream != '\"') die(SQ_PE_INVALID_MACRO, "can only compile strings");
 	char *filename = parse_text().text->ptr; // lol memfree?
 
 	if (!should_compile(filename)) return;
@@ -320,7 +316,7 @@ static void parse_transcribe(void) {
 
 	// this _will_ leak memory, but eh we're compiling who cares
 	memcpy(new_stream + file_size, sq_stream, stream_len + 1);
-	sq_stream = new_stream;
+	switch_stream(new_stream);
 }
 
 
@@ -329,17 +325,17 @@ static void parse_macro_statement(char *name) {
 	else if (!strcmp(name, "nevermore")) parse_nevermore();
 	else if (!strcmp(name, "transcribe")) parse_transcribe();
 	else if (!strcmp(name, "whereupon")) parse_whereupon();
-	else if (!strcmp(name, "nowhere")) die("unexpected '@nowhere'");
-	else if (!strcmp(name, "alas")) die("unexpected '@alas'");
+	else if (!strcmp(name, "nowhere")) die(SQ_PE_INVALID_MACRO, "unexpected '@nowhere'");
+	else if (!strcmp(name, "alas")) die(SQ_PE_INVALID_MACRO, "unexpected '@alas'");
 	else if (!strcmp(name, "expand")) { /* parse_expand(); */ }
-	else die("unknown macro statement kind '%s'", name);;
+	else die(SQ_PE_INVALID_MACRO, "unknown macro statement kind '%s'", name);;
 
 	free(name);
 }
 
 static void	parse_macro_identifier_invocation(struct expansion *exp, struct macro_variable *var) {
 	if (sq_next_token().kind != SQ_TK_LPAREN)
-		die("expected '(' after macro function '%s'", var->name);
+		die(SQ_PE_INVALID_MACRO, "expected '(' after macro function '%s'", var->name);
 
 	struct {
 		struct sq_token *args;
@@ -358,15 +354,11 @@ static void	parse_macro_identifier_invocation(struct expansion *exp, struct macr
 
 		while (true) {
 			switch ((token = sq_next_token()).kind) {
-			case SQ_TK_LTH:
-				if (*sq_stream != '<') break;
-				++sq_stream;
+			case SQ_TK_SHL:
 				is_verbatim = true;
 				continue;
 
-			case SQ_TK_GTH:
-				if (*sq_stream != '>') break;
-				++sq_stream;
+			case SQ_TK_SHR:
 				is_verbatim = false;
 				continue;
 
@@ -377,7 +369,7 @@ static void	parse_macro_identifier_invocation(struct expansion *exp, struct macr
 			case SQ_TK_RPAREN:
 				if (!is_verbatim && !paren_depth--) {
 					if (i != var->arglen - 1)
-						die("unexpected `)`; too few arguments");
+						die(SQ_PE_INVALID_MACRO, "unexpected `)`; too few arguments");
 					goto next_arg;
 				}
 				break;
@@ -402,7 +394,7 @@ static void	parse_macro_identifier_invocation(struct expansion *exp, struct macr
 	}
 
 	if (!var->arglen && sq_next_token().kind != SQ_TK_RPAREN)
-		die("missing closing ')'");
+		die(SQ_PE_INVALID_MACRO, "missing closing ')'");
 
 	// expand them out and make the resulting array
 	len = 0;
@@ -460,12 +452,12 @@ static bool parse_macro_identifier(char *name) {
 		if (!strcmp(name, (var = &variables.vars[i])->name))
 			goto found;
 
-	die("unknown macro identifier '$%s'", name);
+	die(SQ_PE_INVALID_MACRO, "unknown macro identifier '$%s'", name);
 
 found:
 	// free(name);
 	if (MAX_EXPANSIONS < expansion_pos)
-		die("too many expansions!");
+		die(SQ_PE_LIMIT_EXCEEDED, "too many expansions!");
 
 	struct expansion exp;
 
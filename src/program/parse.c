@@ -7,9 +7,16 @@
 
 #include <string.h>
 
+// parse errors are reported at the start of the most recently taken token.
+#undef die
+#define die(...) sq_parse_error(last.position, __VA_ARGS__)
+
 struct sq_token last;
 bool rewound;
 
+// set when a label was used as an identifier, so the `:` it swallowed is taken next.
+static bool pending_colon;
+
 static void untake() {
 	assert(!rewound);
 	rewound = true;
@@ -18,6 +25,9 @@ static void untake() {
 static struct sq_token take_endline() {
 	if (rewound) {
 		rewound = false;
+	} else if (pending_colon) {
+		pending_colon = false;
+		last.kind = SQ_TK_COLON;
 	} else {
 		last = sq_next_token();
 	}
@@ -34,7 +44,7 @@ static struct sq_token take() {
 
 #define EXPECTED(kind_, iffalse) \
 	do { if (take().kind != kind_) { iffalse; } } while(0)
-#define EXPECT(kind_, ...) EXPECTED(kind_, die(__VA_ARGS__))
+#define EXPECT(kind_, ...) EXPECTED(kind_, die(SQ_PE_EXPECTED_TOKEN, __VA_ARGS__))
 #define GUARD(kind_) EXPECTED(kind_, untake(); return NULL)
 
 static struct expression *parse_expression(void);
@@ -42,6 +52,11 @@ static struct expression *parse_expression(void);
 static char *token_to_identifier(struct sq_token token) {
 	switch (token.kind) {
 	case SQ_TK_IDENT: return last.identifier;
+	// labels are just identifiers followed by a `:`, such as the `b` in `a ? b : c`.
+	case SQ_TK_LABEL:
+		last.kind = SQ_TK_IDENT;
+		pending_colon = true;
+		return last.identifier;
 	case SQ_TK_EQL: return strdup("==");
 	case SQ_TK_LTH: return strdup("<");
 	case SQ_TK_LEQ: return strdup("<=");
@@ -55,6 +70,11 @@ static char *token_to_identifier(struct sq_token token) {
 	case SQ_TK_POW: return strdup("^");
 	case SQ_TK_DIV: return strdup("/");
 	case SQ_TK_MOD: return strdup("%");
+	case SQ_TK_BAND: return strdup("&");
+	case SQ_TK_BOR: return strdup("|");
+	case SQ_TK_BXOR: return strdup("~");
+	case SQ_TK_SHL: return strdup("<<");
+	case SQ_TK_SHR: return strdup(">>");
 	case SQ_TK_INDEX: return strdup("[]");
 	case SQ_TK_INDEX_ASSIGN: return strdup("[]=");
 	default: return NULL;
@@ -79,18 +99,28 @@ static struct variable_old *parse_variable(void) {
 
 static struct function_call_old *parse_func_call_old(struct variable_old *func) {
 	struct expression *args[SQ_JOURNEY_MAX_ARGC];
-	unsigned arg_count = 0;
-
-	while (take().kind != SQ_TK_RPAREN && arg_count <= SQ_JOURNEY_MAX_ARGC) {
-		if (last.kind == SQ_TK_UNDEFINED) die("missing rparen for fn call");
-		untake();
+	struct keyword_argument kwargs[SQ_JOURNEY_MAX_ARGC];
+	unsigned arg_count = 0, kwargc = 0;
+
+	while (take().kind != SQ_TK_RPAREN && arg_count <= SQ_JOURNEY_MAX_ARGC && kwargc <= SQ_JOURNEY_MAX_ARGC) {
+		if (last.kind == SQ_TK_UNDEFINED) die(SQ_PE_EXPECTED_TOKEN, "missing rparen for fn call");
+
+		if (last.kind == SQ_TK_LABEL) {
+			kwargs[kwargc].name = last.identifier;
+			if (!(kwargs[kwargc++].value = parse_expression()))
+				die(SQ_PE_INVALID_ARGUMENTS, "invalid keyword argument '%s' found in function call", kwargs[kwargc - 1].name);
+		} else if (kwargc) {
+			die(SQ_PE_INVALID_ARGUMENTS, "positional arguments may not follow keyword arguments");
+		} else {
+			untake();
 
-		if (!(args[arg_count++] = parse_expression()))
-			die("invalid argument #%d found in function call", arg_count-1);
+			if (!(args[arg_count++] = parse_expression()))
+				die(SQ_PE_INVALID_ARGUMENTS, "invalid argument #%d found in function call", arg_count-1);
+		}
 
 		if (take().kind != SQ_TK_COMMA) {
 			if (last.kind != SQ_TK_RPAREN)
-				die("missing rparen for fn call");
+				die(SQ_PE_EXPECTED_TOKEN, "missing rparen for fn call");
 			break;
 		}
 	}
@@ -98,33 +128,45 @@ static struct function_call_old *parse_func_call_old(struct variable_old *func)
 	struct function_call_old *fncall = xmalloc(sizeof(struct function_call_old));
 	fncall->func = func;
 	fncall->args = memdup(args, sizeof_array(struct expression *, arg_count));
+	fncall->kwargs = memdup(kwargs, sizeof_array(struct keyword_argument, kwargc));
 
 	fncall->arglen = arg_count;
+	fncall->kwargc = kwargc;
 
 	return fncall;
 }
 
 static void parse_func_call(struct function_call *fncall) {
 	struct expression *args[SQ_JOURNEY_MAX_ARGC];
-	fncall->argc = 0;
+	struct keyword_argument kwargs[SQ_JOURNEY_MAX_ARGC];
+	fncall->argc = fncall->kwargc = 0;
 
-	while (take().kind != SQ_TK_RPAREN && fncall->argc <= SQ_JOURNEY_MAX_ARGC) {
+	while (take().kind != SQ_TK_RPAREN && fncall->argc <= SQ_JOURNEY_MAX_ARGC && fncall->kwargc <= SQ_JOURNEY_MAX_ARGC) {
 		if (last.kind == SQ_TK_UNDEFINED)
-			die("missing rparen for fn call");
-
-		untake();
+			die(SQ_PE_EXPECTED_TOKEN, "missing rparen for fn call");
+
+		if (last.kind == SQ_TK_LABEL) {
+			kwargs[fncall->kwargc].name = last.identifier;
+			if (!(kwargs[fncall->kwargc++].value = parse_expression()))
+				die(SQ_PE_INVALID_ARGUMENTS, "invalid keyword argument '%s' found in function call", kwargs[fncall->kwargc - 1].name);
+		} else if (fncall->kwargc) {
+			die(SQ_PE_INVALID_ARGUMENTS, "positional arguments may not follow keyword arguments");
+		} else {
+			untake();
 
-		if (!(args[fncall->argc++] = parse_expression()))
-			die("invalid argument #%d found in function call", fncall->argc-1);
+			if (!(args[fncall->argc++] = parse_expression()))
+				die(SQ_PE_INVALID_ARGUMENTS, "invalid argument #%d found in function call", fncall->argc-1);
+		}
 
 		if (take().kind != SQ_TK_COMMA) {
 			if (last.kind != SQ_TK_RPAREN)
-				die("missing rparen for fn call");
+				die(SQ_PE_EXPECTED_TOKEN, "missing rparen for fn call");
 			break;
 		}
 	}
 
 	fncall->args = memdup(args, sizeof_array(struct expression *, fncall->argc));
+	fncall->kwargs = memdup(kwargs, sizeof_array(struct keyword_argument, fncall->kwargc));
 }
 
 // static struct index *parse_index(struct primary *primary) {
@@ -143,7 +185,7 @@ static struct book *parse_book() {
 
 	while ((take(),untake(),last.kind != SQ_TK_RBRACKET)) {
 		if (last.kind == SQ_TK_UNDEFINED)
-			die("missing rparen for book initialization");
+			die(SQ_PE_EXPECTED_TOKEN, "missing rparen for book initialization");
 
 		if (len == cap)
 			pages = xrealloc(pages, sizeof_array(struct expression, cap *= 2));
@@ -171,7 +213,7 @@ static struct dict *parse_codex() {
 
 	while ((take(),untake(),last.kind != SQ_TK_RBRACE)) {
 		if (last.kind == SQ_TK_UNDEFINED)
-			die("missing rparen for codex call");
+			die(SQ_PE_EXPECTED_TOKEN, "missing rparen for codex call");
 
 		if (len == cap) {
 			cap *= 2;
@@ -235,6 +277,10 @@ static struct primary *parse_primary() {
 		primary.kind = SQ_PS_PNUMERAL;
 		primary.numeral = last.numeral;
 		break;
+	case SQ_TK_DECIMAL:
+		primary.kind = SQ_PS_PDECIMAL;
+		primary.decimal = last.decimal;
+		break;
 	case SQ_TK_TEXT:
 		primary.kind = SQ_PS_PTEXT;
 		primary.text = last.text;
@@ -250,7 +296,8 @@ static struct primary *parse_primary() {
 	case SQ_TK_NI:
 		primary.kind = SQ_PS_PNI;
 		break;
-	case SQ_TK_IDENT: {
+	case SQ_TK_IDENT:
+	case SQ_TK_LABEL: {
 		untake();
 		struct variable_old *var = parse_variable();
 
@@ -287,7 +334,9 @@ static struct primary *parse_primary() {
 reparse_primary:
 	prim_ptr = memdup(&primary, sizeof(struct primary));
 
-	switch (take().kind) {
+	// calls, indexing, and field accesses must start on the same line, otherwise
+	// a parenthesized statement after a newline would be parsed as a call.
+	switch (take_endline().kind) {
 	case SQ_TK_LPAREN:
 		primary.kind = SQ_PS_PFNCALL;
 
@@ -307,7 +356,7 @@ reparse_primary:
 		primary.kind = SQ_PS_PINDEX;
 		primary.index.into = prim_ptr;
 		if (!(primary.index.index = parse_expression()))
-			die("Cant parse index expression");
+			die(SQ_PE_MISSING_EXPRESSION, "Cant parse index expression");
 		EXPECT(SQ_TK_RBRACKET, "expected a ']' at end of index");
 		goto reparse_primary;
 
@@ -339,9 +388,14 @@ static struct unary_expression *parse_unary_expression() {
 	default:
 		unary.kind = SQ_PS_UPRIMARY;
 		untake();
+
+		if (!(unary.rhs = parse_primary()))
+			return NULL;
+
+		return memdup(&unary, sizeof(struct unary_expression));
 	}
 
-	if (!(unary.rhs = parse_primary()))
+	if (!(unary.operand = parse_unary_expression()))
 		return NULL;
 
 	return memdup(&unary, sizeof(struct unary_expression));
@@ -364,7 +418,7 @@ static struct pow_expression *parse_pow_expression() {
 
 	if (pow.kind != SQ_PS_PUNARY) {
 		if (!(pow.rhs = parse_pow_expression()))
-			die("missing right-hand side for pow-like operation");
+			die(SQ_PE_MISSING_EXPRESSION, "missing right-hand side for pow-like operation");
 	}
 
 	return memdup(&pow, sizeof(struct pow_expression));
@@ -386,6 +440,15 @@ static struct mul_expression *parse_mul_expression() {
 	case SQ_TK_MOD:
 		mul.kind = SQ_PS_MMOD;
 		break;
+	case SQ_TK_BAND:
+		mul.kind = SQ_PS_MBAND;
+		break;
+	case SQ_TK_SHL:
+		mul.kind = SQ_PS_MSHL;
+		break;
+	case SQ_TK_SHR:
+		mul.kind = SQ_PS_MSHR;
+		break;
 	default:
 		mul.kind = SQ_PS_MPOW;
 		untake();
@@ -393,7 +456,7 @@ static struct mul_expression *parse_mul_expression() {
 
 	if (mul.kind != SQ_PS_MPOW) {
 		if (!(mul.rhs = parse_mul_expression()))
-			die("missing right-hand side for mul-like operation");
+			die(SQ_PE_MISSING_EXPRESSION, "missing right-hand side for mul-like operation");
 	}
 
 	return memdup(&mul, sizeof(struct mul_expression));
@@ -412,6 +475,12 @@ static struct add_expression *parse_add_expression() {
 	case SQ_TK_SUB:
 		add.kind = SQ_PS_ASUB;
 		break;
+	case SQ_TK_BOR:
+		add.kind = SQ_PS_ABOR;
+		break;
+	case SQ_TK_BXOR:
+		add.kind = SQ_PS_ABXOR;
+		break;
 	default:
 		add.kind = SQ_PS_AMUL;
 		untake();
@@ -419,7 +488,7 @@ static struct add_expression *parse_add_expression() {
 
 	if (add.kind != SQ_PS_AMUL) {
 		if (!(add.rhs = parse_add_expression()))
-			die("missing right-hand side for add-like operation");
+			die(SQ_PE_MISSING_EXPRESSION, "missing right-hand side for add-like operation");
 	}
 
 	return memdup(&add, sizeof(struct add_expression));
@@ -447,6 +516,9 @@ static struct cmp_expression *parse_cmp_expression() {
 	case SQ_TK_CMP:
 		cmp.kind = SQ_PS_CCMP;
 		break;
+	case SQ_TK_DOTDOT:
+		cmp.kind = SQ_PS_CRANGE;
+		break;
 	default:
 		cmp.kind = SQ_PS_CADD;
 		untake();
@@ -454,7 +526,7 @@ static struct cmp_expression *parse_cmp_expression() {
 
 	if (cmp.kind != SQ_PS_CADD) {
 		if (!(cmp.rhs = parse_cmp_expression()))
-			die("missing right-hand side for cmp-like operation");
+			die(SQ_PE_MISSING_EXPRESSION, "missing right-hand side for cmp-like operation");
 	}
 
 	return memdup(&cmp, sizeof(struct cmp_expression));
@@ -483,7 +555,7 @@ static struct eql_expression *parse_eql_expression() {
 
 	if (eql.kind != SQ_PS_ECMP) {
 		if (!(eql.rhs = parse_eql_expression()))
-			die("missing right-hand side for eql-like operation");
+			die(SQ_PE_MISSING_EXPRESSION, "missing right-hand side for eql-like operation");
 	}
 
 	return memdup(&eql, sizeof(struct eql_expression));
@@ -509,19 +581,47 @@ static struct bool_expression *parse_bool_expression() {
 
 	if (eql.kind != SQ_PS_BEQL) {
 		if (!(eql.rhs = parse_bool_expression()))
-			die("missing right-hand side for veracity-like operation");
+			die(SQ_PE_MISSING_EXPRESSION, "missing right-hand side for veracity-like operation");
 	}
 
 	return memdup(&eql, sizeof(struct bool_expression));
 }
 
-static struct assignment *parse_assignment(struct variable_old *var) {
-	GUARD(SQ_TK_ASSIGN);
+static bool is_assignment_token(enum sq_token_kind kind) {
+	switch (kind) {
+	case SQ_TK_ASSIGN:
+	case SQ_TK_ADD_ASSIGN:
+	case SQ_TK_SUB_ASSIGN:
+	case SQ_TK_MUL_ASSIGN:
+	case SQ_TK_DIV_ASSIGN:
+	case SQ_TK_MOD_ASSIGN:
+	case SQ_TK_POW_ASSIGN:
+		return true;
+	default:
+		return false;
+	}
+}
 
+static struct assignment *parse_assignment(struct variable_old *var) {
 	struct assignment *asgn = xmalloc(sizeof(struct assignment));
+
+	switch (take().kind) {
+	case SQ_TK_ASSIGN: asgn->kind = SQ_PS_ASGN_PLAIN; break;
+	case SQ_TK_ADD_ASSIGN: asgn->kind = SQ_PS_ASGN_ADD; break;
+	case SQ_TK_SUB_ASSIGN: asgn->kind = SQ_PS_ASGN_SUB; break;
+	case SQ_TK_MUL_ASSIGN: asgn->kind = SQ_PS_ASGN_MUL; break;
+	case SQ_TK_DIV_ASSIGN: asgn->kind = SQ_PS_ASGN_DIV; break;
+	case SQ_TK_MOD_ASSIGN: asgn->kind = SQ_PS_ASGN_MOD; break;
+	case SQ_TK_POW_ASSIGN: asgn->kind = SQ_PS_ASGN_POW; break;
+	default:
+		untake();
+		free(asgn);
+		return NULL;
+	}
+
 	asgn->var = var;
 	if (!(asgn->expr = parse_expression()))
-		die("missing rhs for assignment");
+		die(SQ_PE_MISSING_EXPRESSION, "missing rhs for assignment");
 	return asgn;
 }
 
@@ -534,7 +634,7 @@ static struct index_assign *parse_index_assign(struct index *aidx) {
 	ary_asgn->index = aidx->index;
 
 	if (!(ary_asgn->value = parse_expression()))
-		die("cannot parse value for ary assignment");
+		die(SQ_PE_MISSING_EXPRESSION, "cannot parse value for ary assignment");
 
 	return ary_asgn;
 }
@@ -548,6 +648,25 @@ static struct expression *parse_expression() {
 	if (!(expr.math = parse_bool_expression()))
 		return NULL;
 
+	if (take().kind == SQ_TK_QUESTION) {
+		struct ternary *ternary = xmalloc(sizeof(struct ternary));
+		ternary->cond = expr.math;
+		expr.kind = SQ_PS_ETERNARY;
+		expr.ternary = ternary;
+
+		if (!(ternary->iftrue = parse_expression()))
+			die(SQ_PE_MISSING_EXPRESSION, "missing value after '?'");
+
+		EXPECT(SQ_TK_COLON, "expected a ':' after the '?' value");
+
+		if (!(ternary->iffalse = parse_expression()))
+			die(SQ_PE_MISSING_EXPRESSION, "missing value after ':'");
+
+		return memdup(&expr, sizeof(struct expression));
+	}
+
+	untake();
+
 	return parse_expression_inner(memdup(&expr, sizeof(struct expression)));
 }
 
@@ -578,7 +697,7 @@ static struct expression *parse_expression_inner(struct expression *expr) {
 		// return parse_expression_inner(expr);
 	}
 
-	if (last.kind == SQ_TK_ASSIGN && prim->kind == SQ_PS_PVARIABLE) {
+	if (is_assignment_token(last.kind) && prim->kind == SQ_PS_PVARIABLE) {
 		struct variable_old *var = xmalloc(sizeof(struct variable_old));
 		var->name = prim->variable;
 		var->field = NULL;
@@ -586,7 +705,7 @@ static struct expression *parse_expression_inner(struct expression *expr) {
 		prim->variable_old = var;
 	}
 
-	if (last.kind == SQ_TK_ASSIGN && prim->kind == SQ_PS_PVARIABLE_OLD) {
+	if (is_assignment_token(last.kind) && prim->kind == SQ_PS_PVARIABLE_OLD) {
 		expr->kind = SQ_PS_EASSIGN;
 		expr->asgn = parse_assignment(prim->variable_old);
 	}
@@ -607,7 +726,7 @@ struct kingdom_declaration *parse_kingdom_declaration() {
 	GUARD(SQ_TK_KINGDOM);
 
 	if (MAX_KINGDOMS <= current_kingdom)
-		die("too many nested kingdoms");
+		die(SQ_PE_LIMIT_EXCEEDED, "too many nested kingdoms");
 
 	// this is terrible
 	struct variable_old *var = parse_variable();
@@ -617,7 +736,7 @@ struct kingdom_declaration *parse_kingdom_declaration() {
 
 	while (var->field) {
 		if (!var->is_namespace_access)
-			die("expected a namespace access, not %s.%s", kingdom->name, var->field);
+			die(SQ_PE_UNEXPECTED_TOKEN, "expected a namespace access, not %s.%s", kingdom->name, var->field);
 
 		kingdom->name = xrealloc(kingdom->name, strlen(kingdom->name) + strlen(var->field->name) + 3);
 		strcat(kingdom->name, "::");
@@ -640,6 +759,9 @@ static struct scope_declaration *parse_global_declaration() {
 
 	EXPECT(SQ_TK_IDENT, "expected an identifier after 'renowned'");
 	global->name = last.identifier;
+	global->nnames = 0;
+	global->names = NULL;
+	global->rest = NULL;
 	if (take().kind == SQ_TK_ASSIGN) {
 		global->value = parse_expression();
 	} else {
@@ -650,13 +772,51 @@ static struct scope_declaration *parse_global_declaration() {
 	return global;
 }
 static struct scope_declaration *parse_local_declaration() {
-	GUARD(SQ_TK_LOCAL);
+	// `sworn` is just like `nigh`, except the variables can't be reassigned.
+	if (take().kind != SQ_TK_LOCAL && last.kind != SQ_TK_SWORN) {
+		untake();
+		return NULL;
+	}
+
 	struct scope_declaration *local = xmalloc(sizeof(struct scope_declaration));
+	local->sworn = last.kind == SQ_TK_SWORN;
+	const char *keyword = local->sworn ? "sworn" : "nigh";
 
-	EXPECT(SQ_TK_IDENT, "expected an identifier after 'nigh'");
+	EXPECT(SQ_TK_IDENT, "expected an identifier after '%s'", keyword);
 	local->name = last.identifier;
-	if (take().kind == SQ_TK_ASSIGN) {
+	local->nnames = 0;
+	local->names = NULL;
+	local->rest = NULL;
+
+	// `nigh a, b, *rest = book` destructures a book, with an optional `*rest` at the end.
+	if (take().kind == SQ_TK_COMMA) {
+		unsigned cap = 4;
+		local->names = xmalloc(sizeof_array(char *, cap));
+
+		do {
+			if (take().kind == SQ_TK_MUL) {
+				EXPECT(SQ_TK_IDENT, "expected an identifier after '*' in '%s'", keyword);
+				local->rest = last.identifier;
+				break;
+			}
+
+			untake();
+			EXPECT(SQ_TK_IDENT, "expected an identifier after ',' in '%s'", keyword);
+
+			if (cap == local->nnames)
+				local->names = xrealloc(local->names, sizeof_array(char *, cap *= 2));
+			local->names[local->nnames++] = last.identifier;
+		} while (take().kind == SQ_TK_COMMA);
+
+		if (local->rest == NULL)
+			untake();
+
+		EXPECT(SQ_TK_ASSIGN, "expected '=' after the names in a destructuring '%s'", keyword);
+		local->value = parse_expression();
+	} else if (last.kind == SQ_TK_ASSIGN) {
 		local->value = parse_expression();
+	} else if (local->sworn) {
+		die(SQ_PE_EXPECTED_TOKEN, "expected '=' after the name in 'sworn'");
 	} else {
 		untake();
 		local->value = NULL;
@@ -722,18 +882,18 @@ static struct form_declaration *parse_form_declaration() {
 			struct journey_declaration *fn = parse_journey_declaration(false, 1, true);
 			if (tkn.kind == SQ_TK_CONSTRUCTOR) {
 				if (fdecl->constructor != NULL)
-					die("cannot have two constructors.");
+					die(SQ_PE_DUPLICATE_DECLARATION, "cannot have two constructors.");
 				fdecl->constructor = fn;
 			} else if (tkn.kind == SQ_TK_METHOD) {
 				if (fdecl->nmeths >= MAX_LEN)
-					die("too many methods!");
+					die(SQ_PE_LIMIT_EXCEEDED, "too many methods!");
 				fdecl->meths[fdecl->nmeths++] = fn;
 			} else if (tkn.kind == SQ_TK_CLASSFN) {
 				if (fdecl->nfuncs >= MAX_LEN)
-					die("too many form methods!");
+					die(SQ_PE_LIMIT_EXCEEDED, "too many form methods!");
 				fdecl->funcs[fdecl->nfuncs++] = fn;
 			} else {
-				die("[bug] its not a constructor, func, or formfn?");
+				die(SQ_PE_UNEXPECTED_TOKEN, "[bug] its not a constructor, func, or formfn?");
 			}
 
 			break;
@@ -742,7 +902,7 @@ static struct form_declaration *parse_form_declaration() {
 		case SQ_TK_ESSENCE:
 			while (take().kind == SQ_TK_IDENT || last.kind == SQ_TK_LABEL) {
 				if (fdecl->nessences > MAX_LEN)
-					die("too many essences!");
+					die(SQ_PE_LIMIT_EXCEEDED, "too many essences!");
 
 				fdecl->essences[fdecl->nessences++].name = last.identifier;
 				fdecl->essences[fdecl->nessences - 1].value = NULL;
@@ -775,7 +935,7 @@ static struct form_declaration *parse_form_declaration() {
 				}
 
 				if (fdecl->nmatter > MAX_LEN)
-					die("too many fields!");
+					die(SQ_PE_LIMIT_EXCEEDED, "too many fields!");
 
 				fdecl->matter[fdecl->nmatter].name = last.identifier;
 				fdecl->matter[fdecl->nmatter].genus =
@@ -796,7 +956,7 @@ static struct form_declaration *parse_form_declaration() {
 			continue;
 
 		default:
-			die("unknown token encountered when parsing 'form'.");
+			die(SQ_PE_UNEXPECTED_TOKEN, "unknown token encountered when parsing 'form'.");
 		}
 	}
 
@@ -819,7 +979,7 @@ static struct statements *parse_brace_statements(char *what) {
 	struct statements *stmts;
 	EXPECT(SQ_TK_LBRACE, "missing '{' for '%s' body", what);
 	if (!(stmts = parse_statements()))
-		die("missing body for '%s'", what);
+		die(SQ_PE_EXPECTED_TOKEN, "missing body for '%s'", what);
 
 	EXPECT(SQ_TK_RBRACE, "missing '}' for '%s' body", what);
 	return stmts;
@@ -847,13 +1007,13 @@ static void parse_journey_pattern(bool is_method, struct journey_pattern *jp) {
 
 		case SQ_TK_MUL:
 			if (stage == STAGE_KW_ONLY) {
-				die("duplicate splat argument encountered");
+				die(SQ_PE_DUPLICATE_DECLARATION, "duplicate splat argument encountered");
 			} else if (take().kind == SQ_TK_COMMA || last.kind == SQ_TK_RPAREN) {
 				assert(!jp->splat);
 				jp->splat = strdup(""); // make it empty, so it still registers, but isn't accessible
 				untake();
 			} else if (last.kind != SQ_TK_IDENT) {
-				die("expected name (or nothing) after '*'");
+				die(SQ_PE_EXPECTED_TOKEN, "expected name (or nothing) after '*'");
 			} else {
 				assert(!jp->splat);
 				jp->splat = last.identifier;
@@ -870,11 +1030,11 @@ static void parse_journey_pattern(bool is_method, struct journey_pattern *jp) {
 				assert(!jp->splatsplat);
 				jp->splatsplat = last.identifier;
 			} else {
-				die("expected name after '**'");
+				die(SQ_PE_EXPECTED_TOKEN, "expected name after '**'");
 			}
 
 			if (take().kind != SQ_TK_COMMA) untake(); // allow trailing comma
-			if (take().kind != SQ_TK_RPAREN) die("missing closing paren");
+			if (take().kind != SQ_TK_RPAREN) die(SQ_PE_EXPECTED_TOKEN, "missing closing paren");
 			goto done_with_arguments;
 
 		case SQ_TK_IDENT:
@@ -882,11 +1042,11 @@ static void parse_journey_pattern(bool is_method, struct journey_pattern *jp) {
 			// are we a keyword argument or a normal positional one?
 			if (stage == STAGE_KW_ONLY) {
 				if (jp->kwargc == SQ_JOURNEY_MAX_ARGC)
-					die("too many keyword arguments!");
+					die(SQ_PE_LIMIT_EXCEEDED, "too many keyword arguments!");
 				current = &jp->kwargv[jp->kwargc++];
 			} else {
 				if (jp->pargc == SQ_JOURNEY_MAX_ARGC)
-					die("too many positional arguments!");
+					die(SQ_PE_LIMIT_EXCEEDED, "too many positional arguments!");
 				current = &jp->pargv[jp->pargc++];
 			}
 
@@ -895,15 +1055,15 @@ static void parse_journey_pattern(bool is_method, struct journey_pattern *jp) {
 
 			// if we were a label (ie had a colon after it), parse a genus.
 			if (last.kind == SQ_TK_LABEL && !(current->genus = parse_primary()))
-				die("missing genus for argument '%s'", current->name);
+				die(SQ_PE_MISSING_EXPRESSION, "missing genus for argument '%s'", current->name);
 
 			// if the next symbol's an `=`, then parse the default value.
 			if (take().kind == SQ_TK_ASSIGN) {
 				if (!(current->default_ = parse_expression()))
-					die("missing default for argument '%s'", current->name);
-				stage = STAGE_DEFAULT;
+					die(SQ_PE_MISSING_EXPRESSION, "missing default for argument '%s'", current->name);
+				if (stage != STAGE_KW_ONLY) stage = STAGE_DEFAULT;
 			} else if (stage == STAGE_DEFAULT) {
-				die("positional parameter after default ones");
+				die(SQ_PE_INVALID_ARGUMENTS, "positional parameter after default ones");
 			} else {
 				untake();
 			}
@@ -911,7 +1071,7 @@ static void parse_journey_pattern(bool is_method, struct journey_pattern *jp) {
 			break;
 
 		default:
-			die("unexpected token encountered when parsing arguments: 0x%x", last.kind);
+			die(SQ_PE_UNEXPECTED_TOKEN, "unexpected token encountered when parsing arguments: 0x%x", last.kind);
 		}
 
 		// the next symbol after an argument either be a comma or a rparen
@@ -923,7 +1083,7 @@ static void parse_journey_pattern(bool is_method, struct journey_pattern *jp) {
 			goto done_with_arguments;
 
 		default:
-			die("unknown token within variable declaration list: %d", last.kind);
+			die(SQ_PE_UNEXPECTED_TOKEN, "unknown token within variable declaration list: %d", last.kind);
 		}
 	}
 
@@ -931,13 +1091,13 @@ done_with_arguments:
 
 	if (take().kind == SQ_TK_COLON)  {
 		if (!(jp->return_genus = parse_primary()))
-			die("unable to parse return genus");
+			die(SQ_PE_MISSING_EXPRESSION, "unable to parse return genus");
 	} else {
 		untake();
 	}
 
 	if (take().kind == SQ_TK_IF) {
-		if (!(jp->condition = parse_expression())) die("unable to parse if condition");
+		if (!(jp->condition = parse_expression())) die(SQ_PE_MISSING_EXPRESSION, "unable to parse if condition");
 	} else {
 		untake();
 	}
@@ -957,7 +1117,7 @@ done_with_arguments:
 		return;
 	}
 
-	die("no body given for function");
+	die(SQ_PE_EXPECTED_TOKEN, "no body given for function");
 }
 
 static struct journey_declaration *parse_journey_declaration(bool guard, bool is_method, bool multiple_patterns) {
@@ -971,14 +1131,14 @@ static struct journey_declaration *parse_journey_declaration(bool guard, bool is
 		untake();
 		jd->name = strdup("<anonymous>");
 	} else if (!(jd->name = token_to_identifier(last))) {
-		die("unexpected token in func declaration list");
+		die(SQ_PE_UNEXPECTED_TOKEN, "unexpected token in func declaration list");
 	}
 
 	jd->npatterns = 0;
 
 	do {
 		if (SQ_JOURNEY_MAX_PATTERNS <= jd->npatterns)
-			die("too many patterns encountered");
+			die(SQ_PE_LIMIT_EXCEEDED, "too many patterns encountered");
 
 		parse_journey_pattern(is_method, &jd->patterns[jd->npatterns++]);
 
@@ -993,7 +1153,7 @@ static struct if_statement *parse_if_statement() {
 	GUARD(SQ_TK_IF);
 	struct if_statement *if_stmt = xmalloc(sizeof(struct if_statement));
 	if (!(if_stmt->cond = parse_expression()))
-		die("missing condition for 'if'");
+		die(SQ_PE_MISSING_EXPRESSION, "missing condition for 'if'");
 
 	if_stmt->iftrue = parse_brace_statements("if");
 
@@ -1028,7 +1188,7 @@ static struct switch_statement *parse_switch_statement() {
 	sw_stmt->cases = xmalloc(sizeof_array(struct case_statement, capacity));
 
 	if (!(sw_stmt->cond = parse_expression()))
-		die("missing condition for 'fork'");
+		die(SQ_PE_MISSING_EXPRESSION, "missing condition for 'fork'");
 
 	EXPECT(SQ_TK_LBRACE, "expected a '{' after fork condition");
 
@@ -1037,7 +1197,7 @@ static struct switch_statement *parse_switch_statement() {
 		switch (take().kind) {
 		case SQ_TK_ELSE:
 			EXPECT(SQ_TK_COLON, "expected a ':' after alas");
-			if (sw_stmt->alas) die("cannot declare 'alas' case twice.");
+			if (sw_stmt->alas) die(SQ_PE_DUPLICATE_DECLARATION, "cannot declare 'alas' case twice.");
 			sw_stmt->alas = parse_statements();
 			break;
 
@@ -1074,7 +1234,7 @@ static struct switch_statement *parse_switch_statement() {
 			return sw_stmt;
 
 		default:
-			die("unexpected token; expecting `path` or `}` after fork body");
+			die(SQ_PE_UNEXPECTED_TOKEN, "unexpected token; expecting `path` or `}` after fork body");
 		}
 	}
 }
@@ -1082,13 +1242,62 @@ static struct switch_statement *parse_switch_statement() {
 static struct while_statement *parse_while_statement() {
 	GUARD(SQ_TK_WHILE);
 	struct while_statement *while_stmt = xmalloc(sizeof(struct while_statement));
+	while_stmt->label = NULL;
 	if (!(while_stmt->cond = parse_expression()))
-		die("missing condition for 'whilst'");
+		die(SQ_PE_MISSING_EXPRESSION, "missing condition for 'whilst'");
 
 	while_stmt->body = parse_brace_statements("whilst");
+
+	if (take().kind == SQ_TK_ELSE) {
+		while_stmt->alas = parse_brace_statements("alas");
+	} else {
+		untake();
+		while_stmt->alas = NULL;
+	}
+
 	return while_stmt;
 }
 
+static struct for_statement *parse_for_statement() {
+	GUARD(SQ_TK_FOR);
+	struct for_statement *for_stmt = xmalloc(sizeof(struct for_statement));
+	for_stmt->label = NULL;
+
+	EXPECT(SQ_TK_IDENT, "expected a variable name after 'for'");
+	for_stmt->variable = last.identifier;
+
+	EXPECT(SQ_TK_IN, "expected 'in' after the variable in 'for'");
+
+	if (!(for_stmt->iterable = parse_expression()))
+		die(SQ_PE_MISSING_EXPRESSION, "missing the value to iterate over for 'for'");
+
+	for_stmt->body = parse_brace_statements("for");
+	return for_stmt;
+}
+
+static struct loop_control *parse_loop_control_statement() {
+	bool is_onward;
+
+	switch (take().kind) {
+	case SQ_TK_BREAK: is_onward = false; break;
+	case SQ_TK_CONTINUE: is_onward = true; break;
+	default: untake(); return NULL;
+	}
+
+	struct loop_control *loopctl = xmalloc(sizeof(struct loop_control));
+	loopctl->is_onward = is_onward;
+
+	// note we use `take_endline`, as the label must be on the same line.
+	if (take_endline().kind == SQ_TK_IDENT) {
+		loopctl->label = last.identifier;
+	} else {
+		untake();
+		loopctl->label = NULL;
+	}
+
+	return loopctl;
+}
+
 static struct return_statement *parse_return_statement() {
 	GUARD(SQ_TK_RETURN);
 	struct return_statement *ret_stmt = xmalloc(sizeof(struct return_statement));
@@ -1101,10 +1310,29 @@ static struct return_statement *parse_return_statement() {
 static struct expression *parse_throw_statement() {
 	GUARD(SQ_TK_THROW);
 	struct expression *expression = parse_expression();
-	if (!expression) die("expected expression after 'hark'");
+	if (!expression) die(SQ_PE_MISSING_EXPRESSION, "expected expression after 'catapult'");
 	return expression;
 }
 
+static struct assert_statement *parse_assert_statement() {
+	GUARD(SQ_TK_ASSERT);
+
+	struct assert_statement *challenge = xmalloc(sizeof(struct assert_statement));
+
+	if (!(challenge->cond = parse_expression()))
+		die(SQ_PE_MISSING_EXPRESSION, "expected expression after 'challenge'");
+
+	if (take().kind == SQ_TK_COLON) {
+		if (!(challenge->message = parse_expression()))
+			die(SQ_PE_MISSING_EXPRESSION, "expected a message after ':'");
+	} else {
+		untake();
+		challenge->message = NULL;
+	}
+
+	return challenge;
+}
+
 static struct trycatch_statement *parse_trycatch_statement() {
 	GUARD(SQ_TK_TRY);
 
@@ -1130,7 +1358,7 @@ static char *parse_comefrom_declaration() {
 	GUARD(SQ_TK_COMEFROM);
 
 	if (take().kind != SQ_TK_IDENT)
-		die("expecting an identifier");
+		die(SQ_PE_EXPECTED_TOKEN, "expecting an identifier");
 
 	return last.identifier;
 }
@@ -1144,16 +1372,40 @@ static struct statement *parse_statement() {
 	if ((stmt.kdecl = parse_kingdom_declaration())) stmt.kind = SQ_PS_SKINGDOM;
 	else if ((stmt.gdecl = parse_global_declaration())) stmt.kind = SQ_PS_SGLOBAL;
 	else if ((stmt.ldecl = parse_local_declaration())) stmt.kind = SQ_PS_SLOCAL;
-	else if ((stmt.label = parse_label_declaration())) stmt.kind = SQ_PS_SLABEL;
+	else if ((stmt.label = parse_label_declaration())) {
+		stmt.kind = SQ_PS_SLABEL;
+
+		// labels directly before a `whilst` or `for` also name the loop.
+		if (take().kind == SQ_TK_WHILE) {
+			char *label = stmt.label;
+
+			untake();
+			stmt.wstmt = parse_while_statement();
+			stmt.wstmt->label = label;
+			stmt.kind = SQ_PS_SWHILE;
+		} else if (last.kind == SQ_TK_FOR) {
+			char *label = stmt.label;
+
+			untake();
+			stmt.forstmt = parse_for_statement();
+			stmt.forstmt->label = label;
+			stmt.kind = SQ_PS_SFOR;
+		} else {
+			untake();
+		}
+	}
 	else if ((stmt.comefrom = parse_comefrom_declaration())) stmt.kind = SQ_PS_SCOMEFROM;
 	else if ((stmt.cdecl = parse_form_declaration())) stmt.kind = SQ_PS_SCLASS;
 	else if ((stmt.jdecl = parse_journey_declaration(true, false, true))) stmt.kind = SQ_PS_SJOURNEY;
 	else if ((stmt.ifstmt = parse_if_statement())) stmt.kind = SQ_PS_SIF;
 	else if ((stmt.sw_stmt = parse_switch_statement())) stmt.kind = SQ_PS_SSWITCH;
 	else if ((stmt.wstmt = parse_while_statement())) stmt.kind = SQ_PS_SWHILE;
+	else if ((stmt.forstmt = parse_for_statement())) stmt.kind = SQ_PS_SFOR;
+	else if ((stmt.loopctl = parse_loop_control_statement())) stmt.kind = SQ_PS_SLOOPCONTROL;
 	else if ((stmt.rstmt = parse_return_statement())) stmt.kind = SQ_PS_SRETURN;
 	else if ((stmt.tcstmt = parse_trycatch_statement())) stmt.kind = SQ_PS_STRYCATCH;
 	else if ((stmt.throwstmt = parse_throw_statement())) stmt.kind = SQ_PS_STHROW;
+	else if ((stmt.assertstmt = parse_assert_statement())) stmt.kind = SQ_PS_SASSERT;
 	else if ((stmt.expr = parse_expression())) stmt.kind = SQ_PS_SEXPR;
 	else return NULL;
 
@@ -1164,15 +1416,13 @@ static struct statements *parse_statements() {
 	unsigned cap = 256, len=0;
 	struct statement **list = xmalloc(sizeof_array(struct statement *, cap));
 
-	bool endl = true;
 	while ((list[len] = parse_statement())) {
-		// if (!endl && list[len-1]->kind == SQ_PS_SEXPR) die("missing `;` between statements");
 		if (++len == cap - 1)
 			list = xrealloc(list, sizeof_array(struct statement *, cap*=2));
 
-		endl = false;
-		while (take_endline().kind == SQ_TK_ENDL || last.kind == SQ_TK_SOFT_ENDL)
-			endl = true;
+		while (take_endline().kind == SQ_TK_ENDL || last.kind == SQ_TK_SOFT_ENDL) {
+			// do nothing
+		}
 		untake(); // as the while statement broke it.
 	}
 
@@ -1190,7 +1440,133 @@ static struct statements *parse_statements() {
 struct statements *sq_parse_statements(const char *stream) {
 	last.kind = SQ_TK_UNDEFINED;
 	rewound = false;
-	sq_stream = stream;
-	return parse_statements();
+	pending_colon = false;
+	sq_stream_begin(stream);
+
+	struct statements *stmts = parse_statements();
+
+	// `parse_statements` stops at the first token that can't start a statement, such as a stray `)`.
+	if (take().kind != SQ_TK_UNDEFINED)
+		die(SQ_PE_UNEXPECTED_TOKEN, "unexpected token");
+
+	return stmts;
+}
+
+// Skips the rest of the statement a parse error occurred in, stopping after the next `;`, newline,
+// or `}` which doesn't close a `{` that was skipped.
+static void synchronize(void) {
+	// `volatile`, as it's changed between the `setjmp` and a `longjmp` to it.
+	volatile unsigned depth = 0;
+
+	sq_stream_recover();
+	rewound = false;
+	pending_colon = false;
+
+	if (last.kind == SQ_TK_ENDL || last.kind == SQ_TK_SOFT_ENDL || last.kind == SQ_TK_RBRACE)
+		return;
+
+	while (true) {
+		if (setjmp(exception_handlers[current_exception_handler++])) {
+			// the tokenizer itself failed, so just skip the offending character.
+			sq_value_free(exception);
+			exception = SQ_NI;
+			if (sq_stream_exhausted) return;
+			++sq_stream;
+			sq_stream_recover();
+			continue;
+		}
+
+		take_endline();
+		sq_exception_pop();
+
+		switch (last.kind) {
+		case SQ_TK_UNDEFINED:
+			return;
+
+		case SQ_TK_LBRACE:
+			++depth;
+			break;
+
+		case SQ_TK_RBRACE:
+			if (!depth--) return;
+			break;
+
+		case SQ_TK_ENDL:
+		case SQ_TK_SOFT_ENDL:
+			if (!depth) return;
+			break;
+
+		default:
+			break;
+		}
+	}
+}
+
+unsigned sq_parse_diagnose(const char *stream, struct sq_parse_diagnostic **diagnostics) {
+	// `volatile`, as they're changed between the `setjmp` and a `longjmp` to it.
+	volatile unsigned len = 0, cap = 4;
+	*diagnostics = xmalloc(sizeof_array(struct sq_parse_diagnostic, cap));
+
+	last.kind = SQ_TK_UNDEFINED;
+	rewound = false;
+	pending_colon = false;
+	sq_stream_begin(stream);
+
+	while (true) {
+		sq_parse_error_code = SQ_PE_NONE;
+
+		if (setjmp(exception_handlers[current_exception_handler++])) {
+			if (len == cap)
+				*diagnostics = xrealloc(*diagnostics, sizeof_array(struct sq_parse_diagnostic, cap *= 2));
+
+			struct sq_parse_diagnostic *diagnostic = &(*diagnostics)[len++];
+			diagnostic->code = sq_parse_error_code;
+			diagnostic->position = sq_parse_error_position;
+			diagnostic->message = sq_value_is_text(exception)
+				? strdup(sq_value_as_text(exception)->ptr)
+				: strdup("<unknown error>");
+
+			sq_value_free(exception);
+			exception = SQ_NI;
+
+			if (sq_stream_exhausted)
+				break;
+
+			synchronize();
+			continue;
+		}
+
+		parse_statements();
+
+		// `parse_statements` stops at the first token that can't start a statement. A stray `}`
+		// right after an error is most likely the end of the block the error was in.
+		if (take().kind == SQ_TK_UNDEFINED) {
+			sq_exception_pop();
+			break;
+		}
+
+		if (last.kind != SQ_TK_RBRACE || !len)
+			die(SQ_PE_UNEXPECTED_TOKEN, "unexpected token");
+
+		sq_exception_pop();
+	}
+
+	return len;
+}
+
+enum sq_parse_status sq_parse_partial_statements(const char *stream, struct statements **stmts) {
+	if (setjmp(exception_handlers[current_exception_handler++])) {
+		// running out of input partway through just means there's more to come.
+		if (!sq_stream_exhausted)
+			sq_throw_value(exception);
+
+		sq_value_free(exception);
+		exception = SQ_NI;
+		return SQ_PARSE_INCOMPLETE;
+	}
+
+	*stmts = sq_parse_statements(stream);
+	sq_exception_pop();
+	return SQ_PARSE_COMPLETE;
 }
 
@@ -5,6 +5,7 @@
 #include <squire/shared.h>
 #include <squire/exception.h>
 #include <squire/text.h>
+#include <squire/other/other.h>
 
 #include <stdlib.h>
 #include <time.h>
@@ -30,6 +31,7 @@ void sq_program_run(struct sq_program *program, unsigned argc, const char **argv
 	assert(program->main->patterns[0].pargc == 0);
 	srand(time(NULL));
 
+	sq_value_free(program->globals[0]);
 	program->globals[0] = create_argv(argc, argv);
 	sq_exception_init(program);
 	sq_io_startup(program);
@@ -46,9 +48,38 @@ void sq_program_run(struct sq_program *program, unsigned argc, const char **argv
 }
 
 void sq_program_finish(struct sq_program *program) {
-	for (unsigned i = 0; i < program->nglobals; ++i)
+	for (unsigned i = 0; i < program->nglobals; ++i) {
 		sq_value_free(program->globals[i]);
+		free(program->global_names[i]);
+	}
 
 	free(program->globals);
+	free(program->global_names);
 	sq_journey_free(program->main);
 }
+
+void sq_program_reset(struct sq_program *program) {
+	program->steps = 0;
+	sq_journey_reset_stack();
+
+	current_exception_handler = 0;
+	sq_value_free(exception);
+	exception = SQ_NI;
+}
+
+sq_value sq_program_call(struct sq_program *program, const char *name, struct sq_args args) {
+	for (unsigned i = 0; i < program->nglobals; ++i) {
+		if (strcmp(program->global_names[i], name))
+			continue;
+
+		sq_value journey = program->globals[i];
+
+		if (!sq_value_is_journey(journey) && !(sq_value_is_other(journey)
+			&& sq_value_as_other(journey)->kind == SQ_OK_BUILTIN_JOURNEY))
+			sq_throw("cannot call '%s': it is a %s, not a journey", name, sq_value_typename(journey));
+
+		return sq_value_call(journey, args);
+	}
+
+	sq_throw("cannot call '%s': no such global exists", name);
+}
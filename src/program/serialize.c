@@ -0,0 +1,340 @@
+#include <squire/serialize.h>
+#include <squire/shared.h>
+#include <squire/text.h>
+#include <squire/book.h>
+
+#include <string.h>
+#include <inttypes.h>
+
+struct buffer {
+	unsigned char *bytes;
+	size_t len, cap;
+};
+
+static void write_bytes(struct buffer *buf, const void *bytes, size_t length) {
+	if (buf->cap < buf->len + length) {
+		while (buf->cap < buf->len + length)
+			buf->cap *= 2;
+
+		buf->bytes = xrealloc(buf->bytes, buf->cap);
+	}
+
+	memcpy(buf->bytes + buf->len, bytes, length);
+	buf->len += length;
+}
+
+static void write_u8(struct buffer *buf, uint8_t byte) {
+	write_bytes(buf, &byte, 1);
+}
+
+static void write_u32(struct buffer *buf, uint32_t word) {
+	unsigned char bytes[4];
+
+	for (unsigned i = 0; i < 4; ++i)
+		bytes[i] = (word >> (8 * i)) & 0xff;
+
+	write_bytes(buf, bytes, sizeof(bytes));
+}
+
+static void write_u64(struct buffer *buf, uint64_t word) {
+	unsigned char bytes[8];
+
+	for (unsigned i = 0; i < 8; ++i)
+		bytes[i] = (word >> (8 * i)) & 0xff;
+
+	write_bytes(buf, bytes, sizeof(bytes));
+}
+
+// returns the value that couldn't be serialized, or `SQ_UNDEFINED` if everything was.
+static sq_value write_value(struct buffer *buf, sq_value value) {
+	if (value == SQ_NI) {
+		write_u8(buf, SQ_SERIALIZE_NI);
+	} else if (sq_value_is_veracity(value)) {
+		write_u8(buf, SQ_SERIALIZE_VERACITY);
+		write_u8(buf, sq_value_as_veracity(value));
+	} else if (sq_value_is_numeral(value)) {
+		write_u8(buf, SQ_SERIALIZE_NUMERAL);
+		write_u64(buf, (uint64_t) sq_value_as_numeral(value));
+	} else if (sq_value_is_decimal(value)) {
+		write_u8(buf, SQ_SERIALIZE_DECIMAL);
+		union { sq_decimal decimal; uint64_t bits; } pun = { .decimal = sq_value_as_decimal(value) };
+		write_u64(buf, pun.bits);
+	} else if (sq_value_is_text(value)) {
+		struct sq_text *text = sq_value_as_text(value);
+
+		write_u8(buf, SQ_SERIALIZE_TEXT);
+		write_u32(buf, text->length);
+		write_bytes(buf, text->ptr, text->length);
+	} else if (sq_value_is_book(value)) {
+		struct sq_book *book = sq_value_as_book(value);
+		sq_value failed;
+
+		write_u8(buf, SQ_SERIALIZE_BOOK);
+		write_u32(buf, book->length);
+
+		for (size_t i = 0; i < book->length; ++i)
+			if ((failed = write_value(buf, book->pages[i])) != SQ_UNDEFINED)
+				return failed;
+	} else {
+		return value;
+	}
+
+	return SQ_UNDEFINED;
+}
+
+static void write_constant(struct buffer *buf, sq_value constant) {
+	sq_value failed = write_value(buf, constant);
+
+	if (failed != SQ_UNDEFINED) {
+		free(buf->bytes);
+		sq_throw("cannot serialize a %s constant", sq_value_typename(failed));
+	}
+}
+
+unsigned char *sq_codeblock_serialize(const struct sq_codeblock *code, size_t *length) {
+	struct buffer buf = { .len = 0, .cap = 64 };
+	buf.bytes = xmalloc(buf.cap);
+
+	write_bytes(&buf, SQ_SERIALIZE_MAGIC, strlen(SQ_SERIALIZE_MAGIC));
+	write_u8(&buf, SQ_SERIALIZE_VERSION);
+	write_u32(&buf, code->nlocals);
+	write_u32(&buf, code->codelen);
+	write_u32(&buf, code->nconsts);
+
+	// every variant of `sq_bytecode` is an `unsigned`-sized integer, so we can
+	// just write them out directly.
+	for (unsigned i = 0; i < code->codelen; ++i)
+		write_u32(&buf, code->bytecode[i].index);
+
+	for (unsigned i = 0; i < code->nconsts; ++i)
+		write_constant(&buf, code->consts[i]);
+
+	*length = buf.len;
+	return buf.bytes;
+}
+
+struct reader {
+	const unsigned char *bytes;
+	size_t len, pos;
+};
+
+static bool read_bytes(struct reader *rdr, void *bytes, size_t length) {
+	if (rdr->len - rdr->pos < length)
+		return false;
+
+	memcpy(bytes, rdr->bytes + rdr->pos, length);
+	rdr->pos += length;
+	return true;
+}
+
+static bool read_u8(struct reader *rdr, uint8_t *byte) {
+	return read_bytes(rdr, byte, 1);
+}
+
+static bool read_u32(struct reader *rdr, uint32_t *word) {
+	unsigned char bytes[4];
+
+	if (!read_bytes(rdr, bytes, sizeof(bytes)))
+		return false;
+
+	*word = 0;
+	for (unsigned i = 0; i < 4; ++i)
+		*word |= (uint32_t) bytes[i] << (8 * i);
+
+	return true;
+}
+
+static bool read_u64(struct reader *rdr, uint64_t *word) {
+	unsigned char bytes[8];
+
+	if (!read_bytes(rdr, bytes, sizeof(bytes)))
+		return false;
+
+	*word = 0;
+	for (unsigned i = 0; i < 8; ++i)
+		*word |= (uint64_t) bytes[i] << (8 * i);
+
+	return true;
+}
+
+// returns a description of the problem if `constant` couldn't be read.
+static const char *read_value(struct reader *rdr, sq_value *constant, unsigned depth) {
+	uint8_t tag, veracity;
+	uint64_t numeral;
+	uint32_t length;
+	const char *error;
+	char *ptr;
+
+	if (!read_u8(rdr, &tag))
+		return "truncated value";
+
+	switch (tag) {
+	case SQ_SERIALIZE_NI:
+		*constant = SQ_NI;
+		return NULL;
+
+	case SQ_SERIALIZE_VERACITY:
+		if (!read_u8(rdr, &veracity))
+			return "truncated veracity";
+		if (1 < veracity)
+			return "invalid veracity";
+
+		*constant = sq_value_new((sq_veracity) veracity);
+		return NULL;
+
+	case SQ_SERIALIZE_NUMERAL:
+		if (!read_u64(rdr, &numeral))
+			return "truncated numeral";
+
+		// numerals lose their top `SQ_VSHIFT` bits when they're made into values.
+		if ((sq_numeral) numeral != (sq_numeral) (numeral << SQ_VSHIFT) >> SQ_VSHIFT)
+			return "numeral out of range";
+
+		*constant = sq_value_new((sq_numeral) numeral);
+		return NULL;
+
+	case SQ_SERIALIZE_DECIMAL:
+		if (!read_u64(rdr, &numeral))
+			return "truncated decimal";
+
+		union { uint64_t bits; sq_decimal decimal; } pun = { .bits = numeral };
+		*constant = sq_value_new(pun.decimal);
+		return NULL;
+
+	case SQ_SERIALIZE_TEXT:
+		if (!read_u32(rdr, &length) || rdr->len - rdr->pos < length)
+			return "truncated text";
+
+		ptr = xmalloc(length + 1);
+		read_bytes(rdr, ptr, length);
+		ptr[length] = '\0';
+
+		if (strlen(ptr) != length) {
+			free(ptr);
+			return "text contains a NUL byte";
+		}
+
+		*constant = sq_value_new(sq_text_new2(ptr, length));
+		return NULL;
+
+	case SQ_SERIALIZE_BOOK: {
+		if (depth == SQ_SERIALIZE_MAX_DEPTH)
+			return "books are nested too deeply";
+
+		// every page takes at least one byte, so this stops bogus lengths early.
+		if (!read_u32(rdr, &length) || rdr->len - rdr->pos < length)
+			return "truncated book";
+
+		struct sq_book *book = sq_book_allocate(length);
+
+		for (; book->length < length; ++book->length) {
+			if ((error = read_value(rdr, &book->pages[book->length], depth + 1))) {
+				sq_book_free(book);
+				return error;
+			}
+		}
+
+		*constant = sq_value_new(book);
+		return NULL;
+	}
+
+	default:
+		return "unknown value tag";
+	}
+}
+
+static const char *read_constant(struct reader *rdr, sq_value *constant) {
+	return read_value(rdr, constant, 0);
+}
+
+static void free_partial(struct sq_codeblock *code, unsigned nconsts) {
+	for (unsigned i = 0; i < nconsts; ++i)
+		sq_value_free(code->consts[i]);
+
+	free(code->consts);
+	free(code->bytecode);
+}
+
+void sq_codeblock_deserialize(struct sq_codeblock *code, const unsigned char *bytes, size_t length) {
+	struct reader rdr = { .bytes = bytes, .len = length, .pos = 0 };
+	char magic[sizeof(SQ_SERIALIZE_MAGIC) - 1];
+	const char *error;
+	unsigned offset;
+	uint8_t version;
+	uint32_t nlocals, codelen, nconsts, word;
+
+	if (!read_bytes(&rdr, magic, sizeof(magic)) || memcmp(magic, SQ_SERIALIZE_MAGIC, sizeof(magic)))
+		sq_throw("cannot deserialize: not a serialized codeblock");
+
+	if (!read_u8(&rdr, &version))
+		sq_throw("cannot deserialize: truncated header");
+
+	if (version != SQ_SERIALIZE_VERSION)
+		sq_throw("cannot deserialize: unsupported version %d (expected %d)", version, SQ_SERIALIZE_VERSION);
+
+	if (!read_u32(&rdr, &nlocals) || !read_u32(&rdr, &codelen) || !read_u32(&rdr, &nconsts))
+		sq_throw("cannot deserialize: truncated header");
+
+	// check lengths up front, so bogus ones can't make us allocate huge amounts.
+	if ((rdr.len - rdr.pos) / 4 < codelen || (rdr.len - rdr.pos - codelen * 4) < nconsts)
+		sq_throw("cannot deserialize: truncated body");
+
+	code->nlocals = nlocals;
+	code->codelen = codelen;
+	code->nconsts = nconsts;
+	code->bytecode = xmalloc(sizeof_array(union sq_bytecode, codelen));
+	code->consts = xmalloc(sizeof_array(sq_value, nconsts));
+
+	for (unsigned i = 0; i < codelen; ++i) {
+		read_u32(&rdr, &word);
+		code->bytecode[i].index = word;
+	}
+
+	for (unsigned i = 0; i < nconsts; ++i) {
+		if ((error = read_constant(&rdr, &code->consts[i]))) {
+			free_partial(code, i);
+			sq_throw("cannot deserialize constant %u: %s", i, error);
+		}
+	}
+
+	if (rdr.pos != rdr.len) {
+		free_partial(code, nconsts);
+		sq_throw("cannot deserialize: %zu trailing bytes", rdr.len - rdr.pos);
+	}
+
+	if ((error = sq_codeblock_verify(code, &offset))) {
+		free_partial(code, nconsts);
+		sq_throw("cannot deserialize: invalid bytecode at offset %u: %s", offset, error);
+	}
+}
+
+unsigned char *sq_value_serialize(sq_value value, size_t *length) {
+	struct buffer buf = { .len = 0, .cap = 64 };
+	buf.bytes = xmalloc(buf.cap);
+
+	sq_value failed = write_value(&buf, value);
+
+	if (failed != SQ_UNDEFINED) {
+		free(buf.bytes);
+		sq_throw("cannot serialize a %s", sq_value_typename(failed));
+	}
+
+	*length = buf.len;
+	return buf.bytes;
+}
+
+sq_value sq_value_deserialize(const unsigned char *bytes, size_t length) {
+	struct reader rdr = { .bytes = bytes, .len = length, .pos = 0 };
+	const char *error;
+	sq_value value;
+
+	if ((error = read_value(&rdr, &value, 0)))
+		sq_throw("cannot deserialize value: %s", error);
+
+	if (rdr.pos != rdr.len) {
+		sq_value_free(value);
+		sq_throw("cannot deserialize value: %zu trailing bytes", rdr.len - rdr.pos);
+	}
+
+	return value;
+}
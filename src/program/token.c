@@ -1,12 +1,89 @@
+#define _GNU_SOURCE // for `vasprintf` and `asprintf`
+
 #include <squire/token.h>
 #include <squire/shared.h>
 
 #include <ctype.h>
 #include <string.h>
 #include <stdbool.h>
+#include <stdarg.h>
 
 const char *sq_stream;
+static const char *stream_start, *token_start;
 static char put_back_quote;
+bool sq_stream_exhausted;
+
+// errors in the tokenizer are reported at wherever the stream currently is.
+#undef die
+#define die_at(where, ...) sq_parse_error(sq_stream_position(where), __VA_ARGS__)
+#define die(...) die_at(sq_stream, __VA_ARGS__)
+
+struct sq_position sq_parse_error_position;
+enum sq_parse_error_code sq_parse_error_code;
+
+const char *sq_parse_error_code_name(enum sq_parse_error_code code) {
+	switch (code) {
+	case SQ_PE_NONE: return "E_NONE";
+	case SQ_PE_UNEXPECTED_CHARACTER: return "E_UNEXPECTED_CHARACTER";
+	case SQ_PE_UNTERMINATED_COMMENT: return "E_UNTERMINATED_COMMENT";
+	case SQ_PE_UNTERMINATED_QUOTE: return "E_UNTERMINATED_QUOTE";
+	case SQ_PE_UNTERMINATED_ESCAPE: return "E_UNTERMINATED_ESCAPE";
+	case SQ_PE_INVALID_ESCAPE: return "E_INVALID_ESCAPE";
+	case SQ_PE_INVALID_NUMERAL: return "E_INVALID_NUMERAL";
+	case SQ_PE_NUMERAL_TOO_LARGE: return "E_NUMERAL_TOO_LARGE";
+	case SQ_PE_EXPECTED_TOKEN: return "E_EXPECTED_TOKEN";
+	case SQ_PE_UNEXPECTED_TOKEN: return "E_UNEXPECTED_TOKEN";
+	case SQ_PE_MISSING_EXPRESSION: return "E_MISSING_EXPRESSION";
+	case SQ_PE_INVALID_ARGUMENTS: return "E_INVALID_ARGUMENTS";
+	case SQ_PE_DUPLICATE_DECLARATION: return "E_DUPLICATE_DECLARATION";
+	case SQ_PE_LIMIT_EXCEEDED: return "E_LIMIT_EXCEEDED";
+	case SQ_PE_INVALID_MACRO: return "E_INVALID_MACRO";
+	default: bug("unknown parse error code %d", code);
+	}
+}
+
+void sq_parse_error(struct sq_position position, enum sq_parse_error_code code, const char *fmt, ...) {
+	char *message, *full;
+	va_list args;
+	va_start(args, fmt);
+	vasprintf(&message, fmt, args);
+	va_end(args);
+
+	asprintf(&full, "line %u, column %u: %s", position.line, position.column, message);
+	free(message);
+
+	sq_parse_error_position = position;
+	sq_parse_error_code = code;
+	sq_throw_value(sq_value_new_text(sq_text_new(full)));
+}
+
+// tokens are almost always asked for in order, so we pick up where we last left off.
+static const char *last_where;
+static struct sq_position last_position;
+
+// starts reading from `stream` without resetting the tokenizer, eg for `@transcribe`.
+static void switch_stream(const char *stream) {
+	sq_stream = stream_start = token_start = last_where = stream;
+	last_position.line = last_position.column = 1;
+}
+
+struct sq_position sq_stream_position(const char *where) {
+	if (where < last_where) {
+		last_where = stream_start;
+		last_position.line = last_position.column = 1;
+	}
+
+	for (; last_where < where && *last_where; ++last_where) {
+		if (*last_where == '\n') {
+			++last_position.line;
+			last_position.column = 1;
+		} else if ((*last_where & 0xc0) != 0x80) { // don't count utf-8 continuation bytes
+			++last_position.column;
+		}
+	}
+
+	return last_position;
+}
 
 static struct sq_token next_macro_token(void);
 static void parse_macro_statement(char *);
@@ -37,6 +114,18 @@ static size_t fraktur_length(const char *stream, size_t *index) {
 			return len;
 		}
 	}
+
+	// unlike normal fraktur, bold fraktur (`𝕬` through `𝖟`) is contiguous.
+	const unsigned char *bytes = (const unsigned char *) stream;
+	if (bytes[0] == 0xF0 && bytes[1] == 0x9D && (bytes[2] & 0xC0) == 0x80 && (bytes[3] & 0xC0) == 0x80) {
+		unsigned codepoint = 0x1D000 | ((bytes[2] & 0x3F) << 6) | (bytes[3] & 0x3F);
+
+		if (0x1D56C <= codepoint && codepoint <= 0x1D59F) {
+			*index = codepoint - 0x1D56C;
+			return 4;
+		}
+	}
+
 	return 0;
 }
 
@@ -90,16 +179,23 @@ static void strip_whitespace_maybe_ignore_slash(bool strip_newline, bool ignore_
 			continue;
 		}
 
+		// block comments nest, so `/* a /* b */ c */` is a single comment.
 		if (c == '/' && sq_stream[1] == '*') {
+			const char *start = sq_stream;
+			unsigned depth = 1;
 			sq_stream += 2;
 
-			while (true) {
+			while (depth) {
 				if (sq_stream[0] == '*' && sq_stream[1] == '/') {
 					sq_stream += 2;
-					break;
+					--depth;
+				} else if (sq_stream[0] == '/' && sq_stream[1] == '*') {
+					sq_stream += 2;
+					++depth;
+				} else if (!*sq_stream++) {
+					sq_stream_exhausted = true;
+					die_at(start, SQ_PE_UNTERMINATED_COMMENT, "unterminated block comment");
 				}
-
-				if (!*sq_stream++) die("unterminated block comment");
 			}
 
 			continue;
@@ -143,23 +239,129 @@ static void strip_whitespace(bool strip_newline) {
 
 static unsigned tohex(char c) {
 	if (isdigit(c)) return c - '0';
-	if ('a' <= c && c <= 'f') return c - 'a';
-	if ('A' <= c && c <= 'F') return c - 'A';
-	die("char '%1$c' (\\x%1$02x) isn't a hex digit", c);
+	if ('a' <= c && c <= 'f') return c - 'a' + 10;
+	if ('A' <= c && c <= 'F') return c - 'A' + 10;
+	die(SQ_PE_INVALID_ESCAPE, "char '%1$c' (\\x%1$02x) isn't a hex digit", c);
 }
 
+// Writes `codepoint` to `dst` as utf-8, returning how many bytes were written.
+static unsigned encode_utf8(unsigned codepoint, char *dst) {
+	if (!codepoint || 0x10FFFF < codepoint || (0xD800 <= codepoint && codepoint <= 0xDFFF))
+		die(SQ_PE_INVALID_ESCAPE, "invalid unicode escape '\\u%04x'", codepoint);
+
+	if (codepoint < 0x80) {
+		dst[0] = codepoint;
+		return 1;
+	}
+
+	if (codepoint < 0x800) {
+		dst[0] = 0xC0 | (codepoint >> 6);
+		dst[1] = 0x80 | (codepoint & 0x3F);
+		return 2;
+	}
+
+	if (codepoint < 0x10000) {
+		dst[0] = 0xE0 | (codepoint >> 12);
+		dst[1] = 0x80 | ((codepoint >> 6) & 0x3F);
+		dst[2] = 0x80 | (codepoint & 0x3F);
+		return 3;
+	}
+
+	dst[0] = 0xF0 | (codepoint >> 18);
+	dst[1] = 0x80 | ((codepoint >> 12) & 0x3F);
+	dst[2] = 0x80 | ((codepoint >> 6) & 0x3F);
+	dst[3] = 0x80 | (codepoint & 0x3F);
+	return 4;
+}
+
+// Parses decimal literals, such as `3.14`. Like numerals, their digits can be
+// separated by single `_`s, eg `1_000.000_1`.
+static struct sq_token parse_decimal(void) {
+	struct sq_token token;
+	token.kind = SQ_TK_DECIMAL;
+
+	char *digits = xmalloc(strlen(sq_stream) + 1), *end = digits;
+	bool seen_dot = false;
+
+	for (; isdigit(*sq_stream) || *sq_stream == '_' || (*sq_stream == '.' && !seen_dot); ++sq_stream) {
+		if (*sq_stream == '.')
+			seen_dot = true;
+		else if (*sq_stream == '_' && (!isdigit(sq_stream[-1]) || !isdigit(sq_stream[1])))
+			die(SQ_PE_INVALID_NUMERAL, "underscores in decimal literals must be between two digits");
+
+		if (*sq_stream != '_')
+			*end++ = *sq_stream;
+	}
+
+	*end = '\0';
+	token.decimal = strtod(digits, NULL);
+	free(digits);
+
+	if (isalpha(*sq_stream) || *sq_stream == '_')
+		die(SQ_PE_INVALID_NUMERAL, "invalid trailing characters on decimal literal: %c", *sq_stream);
+
+	return token;
+}
+
+// Parses arabic numeral literals, which can also be hexadecimal (`0x`) or
+// binary (`0b`). Digits can be separated by single `_`s, eg `1_000`.
+//
+// A `.` after the digits only makes the literal a decimal if it's immediately
+// followed by another digit. Otherwise it's left alone, so that `3.` is the
+// numeral `3` followed by a `.`, and `3.foo` is still a field access.
 static struct sq_token parse_arabic_numeral(void) {
 	struct sq_token token;
 	token.kind = SQ_TK_NUMERAL;
 	token.numeral = 0;
 
-	do {
-		token.numeral = token.numeral * 10 + (*sq_stream - '0');
-	} while (isdigit(*++sq_stream));
+	unsigned base = 10, digit;
+	const char *prefix = sq_stream, *after_digits = sq_stream;
 
-	if (isalpha(*sq_stream) || *sq_stream == '_')
-		die("invalid trailing characters on arabic numeral literal: %llu%c\n",
-			(long long) token.numeral, *sq_stream);
+	while (isdigit(*after_digits) || *after_digits == '_')
+		++after_digits;
+
+	if (after_digits[0] == '.' && isdigit(after_digits[1]))
+		return parse_decimal();
+
+	if (sq_stream[0] == '0' && (tolower(sq_stream[1]) == 'x' || tolower(sq_stream[1]) == 'b')) {
+		base = (tolower(sq_stream[1]) == 'x') ? 16 : 2;
+		sq_stream += 2;
+
+		if (*sq_stream == '_')
+			die(SQ_PE_INVALID_NUMERAL, "numeral literals cannot start with a '_'");
+
+		if (!isalnum(*sq_stream))
+			die(SQ_PE_INVALID_NUMERAL, "missing digits after '%.2s' in numeral literal", prefix);
+	}
+
+	for (; isalnum(*sq_stream) || *sq_stream == '_'; ++sq_stream) {
+		if (*sq_stream == '_') {
+			if (sq_stream[1] == '_')
+				die(SQ_PE_INVALID_NUMERAL, "numeral literals cannot contain consecutive '_'s");
+
+			if (!isalnum(sq_stream[1]))
+				die(SQ_PE_INVALID_NUMERAL, "numeral literals cannot end with a '_'");
+
+			continue;
+		}
+
+		digit = isdigit(*sq_stream) ? (unsigned) (*sq_stream - '0')
+			: isxdigit(*sq_stream) ? tohex(*sq_stream)
+			: base;
+
+		if (base <= digit) {
+			if (base == 10)
+				die(SQ_PE_INVALID_NUMERAL, "invalid trailing characters on arabic numeral literal: %llu%c\n",
+					(long long) token.numeral, *sq_stream);
+
+			die(SQ_PE_INVALID_NUMERAL, "invalid digit '%c' in '%.2s' numeral literal", *sq_stream, prefix);
+		}
+
+		if (((INT64_MAX >> SQ_VSHIFT) - digit) / base < token.numeral)
+			die(SQ_PE_NUMERAL_TOO_LARGE, "numeral literal is too large");
+
+		token.numeral = token.numeral * base + digit;
+	}
 
 	return token;
 }
@@ -209,13 +411,30 @@ static struct sq_token next_interpolation_token(void) {
 
 	return token;
 }
-// whelp, `"foo \(bar)!" * 3` will not expand properly, so we need to have
-// every text return a leading `(` first...; but that's too hard rn.
+// so that `"foo \(bar)!" * 3` expands properly, interpolated texts are wrapped
+// in parens, which requires returning a token after the text itself.
+static struct sq_token pending_token;
+static bool has_pending_token;
+
+void sq_stream_begin(const char *stream) {
+	switch_stream(stream);
+	sq_stream_recover();
+}
+
+void sq_stream_recover(void) {
+	// a previous stream may have stopped partway through a token, so reset everything.
+	put_back_quote = '\0';
+	interpolation_length = 0;
+	has_pending_token = false;
+	sq_stream_exhausted = false;
+}
 
 static struct sq_token parse_text(void) {
 	unsigned length = 0;
 	char *dst = xmalloc(strlen(sq_stream));
 	char quote, c;
+	const char *escape;
+	bool is_continuation = put_back_quote, is_interpolating = false;
 
 	if (put_back_quote)
 		quote = put_back_quote, put_back_quote = '\0';
@@ -224,10 +443,13 @@ static struct sq_token parse_text(void) {
 
 	while ((c = *sq_stream++) != quote) {
 	top:
-		if (!c)
-			die("unterminated quote encountered");
+		if (!c) {
+			sq_stream_exhausted = true;
+			die(SQ_PE_UNTERMINATED_QUOTE, "unterminated quote encountered");
+		}
 
-		if (c == '{') {
+		// single-quoted texts are literal, so only double-quoted ones interpolate.
+		if (c == '{' && quote == '"') {
 			_interpolate_is_curly_brace = true;
 			goto interpolate;
 		}
@@ -237,6 +459,8 @@ static struct sq_token parse_text(void) {
 			continue;
 		}
 
+		escape = sq_stream - 1;
+
 		if (quote == '\'') {
 			switch (c = *sq_stream++) {
 				case '\\':
@@ -262,9 +486,11 @@ static struct sq_token parse_text(void) {
 			goto top;
 
 		case '(':
+			_interpolate_is_curly_brace = false;
 		interpolate:
+			is_interpolating = true;
 			if (MAX_INTERPOLATIONS < interpolation_length)
-				die("too many interpolations");
+				die(SQ_PE_LIMIT_EXCEEDED, "too many interpolations");
 
 			interpolations[interpolation_length + 1].depth = 1;
 			interpolations[interpolation_length + 1].quote = quote;
@@ -280,11 +506,52 @@ static struct sq_token parse_text(void) {
 
 		case 'x':
 			if (sq_stream[0] == quote || sq_stream[0] == '\0' || sq_stream[1] == quote)
-				die("unterminated escape sequence");
+				die_at(escape, SQ_PE_UNTERMINATED_ESCAPE, "unterminated escape sequence");
 
 			c = tohex(sq_stream[0]) * 16 + tohex(sq_stream[1]);
 			sq_stream += 2;
+
+			// texts are nul-terminated, so we can't embed a nul in them.
+			if (!c)
+				die_at(escape, SQ_PE_INVALID_ESCAPE, "invalid hex escape '\\x00'");
 			break;
+
+		case 'u': {
+			unsigned codepoint = 0;
+
+			// `\u{...}` form, which can be from one to six digits.
+			if (sq_stream[0] == '{') {
+				unsigned i = 1;
+
+				for (; sq_stream[i] != '}'; ++i) {
+					if (sq_stream[i] == quote || sq_stream[i] == '\0')
+						die_at(escape, SQ_PE_UNTERMINATED_ESCAPE, "unterminated escape sequence");
+
+					if (6 < i)
+						die_at(escape, SQ_PE_INVALID_ESCAPE, "too many digits in unicode escape");
+
+					codepoint = codepoint * 0x10 + tohex(sq_stream[i]);
+				}
+
+				if (i == 1)
+					die_at(escape, SQ_PE_INVALID_ESCAPE, "empty unicode escape");
+
+				sq_stream += i + 1;
+				length += encode_utf8(codepoint, dst + length);
+				continue;
+			}
+
+			for (unsigned i = 0; i < 4; ++i) {
+				if (sq_stream[i] == quote || sq_stream[i] == '\0')
+					die_at(escape, SQ_PE_UNTERMINATED_ESCAPE, "unterminated escape sequence");
+
+				codepoint = codepoint * 0x10 + tohex(sq_stream[i]);
+			}
+
+			sq_stream += 4;
+			length += encode_utf8(codepoint, dst + length);
+			continue;
+		}
 		}
 
 		dst[length++] = c;
@@ -298,6 +565,19 @@ done:
 	token.kind = SQ_TK_TEXT;
 	token.text = sq_text_new2(dst, length);
 
+	if (is_interpolating == is_continuation)
+		return token;
+
+	has_pending_token = true;
+
+	// the start of an interpolated text, so return a `(` and then the text.
+	if (is_interpolating) {
+		pending_token = token;
+		token.kind = SQ_TK_LPAREN;
+	} else {
+		pending_token.kind = SQ_TK_RPAREN;
+	}
+
 	return token;
 }
 
@@ -322,6 +602,8 @@ static struct sq_token parse_identifier(void) {
 			token.identifier[len++] = '_';
 		} else if (*sq_stream == ' ') {
 			while (*++sq_stream == ' ' || sq_stream[-1] == '\t');
+			// `in` isn't part of an identifier, so `for x in y` isn't `for x_in_y`.
+			if (!strncmp(sq_stream, "in", 2) && !isalnum(sq_stream[2]) && sq_stream[2] != '_') break;
 			if (isalnum(*sq_stream) || *sq_stream == '_') token.identifier[len++] = '_';
 			else break;
 		} else break;
@@ -349,11 +631,23 @@ struct sq_token next_non_macro_token() {
 }
 
 struct sq_token sq_next_token() {
+	if (has_pending_token) {
+		has_pending_token = false;
+		return pending_token;
+	}
+
 	struct sq_token token = next_macro_token();
 
 	if (token.kind != SQ_TK_UNDEFINED)
 		return token;
-	return next_non_macro_token();
+
+	token = next_non_macro_token();
+	token.position = sq_stream_position(token_start);
+
+	if (has_pending_token)
+		pending_token.position = token.position;
+
+	return token;
 }
 
 static struct sq_token next_normal_token(void) {
@@ -362,11 +656,13 @@ static struct sq_token next_normal_token(void) {
 	if (put_back_quote) return parse_text();
 
 	strip_whitespace(false);
+	token_start = sq_stream;
 	CHECK_FOR_START("\n", SQ_TK_SOFT_ENDL);
 
 	//printf("<<%s>>\n", sq_stream);
 	if (!*sq_stream || !strncmp(sq_stream, "@__END__", 8)) {
 		//printf("here?\n");
+		sq_stream_exhausted = true;
 		return token.kind = SQ_TK_UNDEFINED, token;
 	}
 
@@ -415,6 +711,7 @@ static struct sq_token next_normal_token(void) {
 	CHECK_FOR_START_KW("journey",      SQ_TK_FUNC);
 	CHECK_FOR_START_KW("renowned",     SQ_TK_GLOBAL);
 	CHECK_FOR_START_KW("nigh",         SQ_TK_LOCAL);
+	CHECK_FOR_START_KW("sworn",        SQ_TK_SWORN);
 
 	CHECK_FOR_START_KW("if",           SQ_TK_IF); // _should_ we have a better one?
 	CHECK_FOR_START_KW("alas",         SQ_TK_ELSE);
@@ -428,6 +725,11 @@ static struct sq_token next_normal_token(void) {
 	CHECK_FOR_START_KW("rejoin",       SQ_TK_REJOIN);
 	CHECK_FOR_START_KW("path",         SQ_TK_CASE);
 	CHECK_FOR_START_KW("kingdom",      SQ_TK_KINGDOM);
+	CHECK_FOR_START_KW("challenge",    SQ_TK_ASSERT);
+	CHECK_FOR_START_KW("flee",         SQ_TK_BREAK);
+	CHECK_FOR_START_KW("onward",       SQ_TK_CONTINUE);
+	CHECK_FOR_START_KW("for",          SQ_TK_FOR);
+	CHECK_FOR_START_KW("in",           SQ_TK_IN);
 
 	CHECK_FOR_START_KW("yea",          SQ_TK_YAY);
 	CHECK_FOR_START_KW("nay",          SQ_TK_NAY);
@@ -448,11 +750,17 @@ static struct sq_token next_normal_token(void) {
 	CHECK_FOR_START(";", SQ_TK_ENDL);
 	CHECK_FOR_START("\n", SQ_TK_SOFT_ENDL);
 	CHECK_FOR_START(",", SQ_TK_COMMA);
+	CHECK_FOR_START("..", SQ_TK_DOTDOT);
 	CHECK_FOR_START(".", SQ_TK_DOT);
 	CHECK_FOR_START("::", SQ_TK_COLONCOLON);
 	CHECK_FOR_START(":", SQ_TK_COLON);
 
+	if (*sq_stream == '?')
+		return ++sq_stream, token.kind = SQ_TK_QUESTION, token;
+
 	CHECK_FOR_START("^=", SQ_TK_POW_ASSIGN);
+	CHECK_FOR_START("**=", SQ_TK_POW_ASSIGN);
+	CHECK_FOR_START("**", SQ_TK_POW);
 	CHECK_FOR_START("*=", SQ_TK_MUL_ASSIGN);
 	CHECK_FOR_START("+=", SQ_TK_ADD_ASSIGN);
 	CHECK_FOR_START("-=", SQ_TK_SUB_ASSIGN);
@@ -467,6 +775,8 @@ static struct sq_token next_normal_token(void) {
 	CHECK_FOR_START(">=", SQ_TK_GEQ);
 	CHECK_FOR_START("=>", SQ_TK_ARROW);
 	CHECK_FOR_START("^", SQ_TK_POW);
+	CHECK_FOR_START("<<", SQ_TK_SHL);
+	CHECK_FOR_START(">>", SQ_TK_SHR);
 	CHECK_FOR_START("<", SQ_TK_LTH);
 	CHECK_FOR_START(">", SQ_TK_GTH);
 	CHECK_FOR_START("+", SQ_TK_ADD);
@@ -478,9 +788,15 @@ static struct sq_token next_normal_token(void) {
 	CHECK_FOR_START("!", SQ_TK_NOT);
 	CHECK_FOR_START("&&", SQ_TK_AND);
 	CHECK_FOR_START("||", SQ_TK_OR);
+	CHECK_FOR_START("&", SQ_TK_BAND);
+	CHECK_FOR_START("|", SQ_TK_BOR);
+	CHECK_FOR_START("~", SQ_TK_BXOR);
 	CHECK_FOR_START("=", SQ_TK_ASSIGN);
 
-	die("unknown token start '%c'", *sq_stream);
+	if (isprint(*sq_stream))
+		die(SQ_PE_UNEXPECTED_CHARACTER, "unexpected character '%c'", *sq_stream);
+	else
+		die(SQ_PE_UNEXPECTED_CHARACTER, "unexpected character '\\x%02x'", (unsigned char) *sq_stream);
 }
 
 
@@ -500,6 +816,7 @@ void sq_token_dump(const struct sq_token *token) {
 
 	case SQ_TK_IDENT: printf("Ident(%s)", token->identifier); break;
 	case SQ_TK_NUMERAL: printf("Numeral(%lld)", (long long) token->numeral); break;
+	case SQ_TK_DECIMAL: printf("Decimal(%g)", token->decimal); break;
 	case SQ_TK_TEXT: printf("Text(%s)", token->text->ptr); break;
 
 	case SQ_TK_LBRACE: printf("Punct({)"); break;
@@ -512,6 +829,7 @@ void sq_token_dump(const struct sq_token *token) {
 	case SQ_TK_SOFT_ENDL: printf("Punct(\\n)"); break;
 	case SQ_TK_COMMA: printf("Punct(,)"); break;
 	case SQ_TK_DOT: printf("Punct(.)"); break;
+	case SQ_TK_DOTDOT: printf("Punct(..)"); break;
 
 	case SQ_TK_EQL: printf("Operator(==)"); break;
 	case SQ_TK_NEQ: printf("Operator(!=)"); break;
@@ -523,6 +841,11 @@ void sq_token_dump(const struct sq_token *token) {
 	case SQ_TK_NOT: printf("Operator(!)"); break;
 	case SQ_TK_AND: printf("Operator(&&)"); break;
 	case SQ_TK_OR: printf("Operator(||)"); break;
+	case SQ_TK_BAND: printf("Operator(&)"); break;
+	case SQ_TK_BOR: printf("Operator(|)"); break;
+	case SQ_TK_BXOR: printf("Operator(~)"); break;
+	case SQ_TK_SHL: printf("Operator(<<)"); break;
+	case SQ_TK_SHR: printf("Operator(>>)"); break;
 	case SQ_TK_ASSIGN: printf("Operator(=)"); break;
 
 	default: printf("Unknown(%d)", token->kind); break;
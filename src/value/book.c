@@ -5,6 +5,7 @@
 #include <squire/exception.h>
 #include <squire/text.h>
 #include <squire/journey.h>
+#include <squire/numeral.h>
 
 struct sq_book *sq_book_new(size_t length, size_t capacity, sq_value *pages) {
 	assert(length <= capacity);
@@ -27,7 +28,7 @@ void sq_book_dump(FILE *out, const struct sq_book *book) {
 		if (i)
 			fprintf(out, ", ");
 
-		sq_value_dump(book->pages[i]);
+		sq_value_dump_to(out, book->pages[i]);
 	}
 
 	putc(')', out);
@@ -44,17 +45,17 @@ void sq_book_deallocate(struct sq_book *book) {
 }
 
 size_t sq_book_fix_index(const struct sq_book *book, ssize_t index) {
-	if (index < 0)
-		index += book->length + 1;
-
-	if (!index--)
+	if (!index)
 		sq_throw("cannot index by N.");
 
-	if (index < 0)
-		sq_throw("index '-%zu' out of bounds!", (size_t) index);
+	if (0 < index)
+		return index - 1;
 
+	if ((ssize_t) book->length < -index)
+		sq_throw("index '%s' out of bounds for a book of length %zu.",
+			sq_numeral_to_roman(index)->ptr, book->length);
 
-	return index;
+	return book->length + index;
 }
 
 
@@ -62,6 +63,8 @@ static void expand_book(struct sq_book *book, size_t length) {
 	if (length < book->length)
 		return;
 
+	sq_value_check_length(length);
+
 	if (book->capacity <= length) {
 		// todo: increase capacity by two.
 		book->capacity = length * 2 + 1;
@@ -162,11 +165,16 @@ struct sq_book *sq_book_repeat(const struct sq_book *book, unsigned amnt) {
 }
 
 struct sq_text *sq_book_join(const struct sq_book *book, const struct sq_text *sep) {
-	unsigned len = 0, cap = 64, seplen = strlen(sep->ptr);
+	size_t len = 0, cap = 64, seplen = strlen(sep->ptr);
 	char *str = xmalloc(cap);
 
-	for (unsigned i = 0; i < book->length; ++i) {
+	for (size_t i = 0; i < book->length; ++i) {
 		if (i) {
+			// free `str` first, as checking the length catapults.
+			if (sq_value_max_length < len + seplen)
+				free(str);
+			sq_value_check_length(len + seplen);
+
 			if (cap <= len + seplen)
 				str = xrealloc(str, cap = cap * 2 + seplen);
 
@@ -175,6 +183,12 @@ struct sq_text *sq_book_join(const struct sq_book *book, const struct sq_text *s
 		}
 
 		struct sq_text *text = sq_value_to_text(book->pages[i]);
+		if (sq_value_max_length < text->length + len) {
+			free(str);
+			sq_text_free(text);
+		}
+		sq_value_check_length(text->length + len);
+
 		if (cap <= text->length + len)
 			str = xrealloc(str, cap = cap * 2 + text->length);
 
@@ -190,13 +204,13 @@ struct sq_text *sq_book_join(const struct sq_book *book, const struct sq_text *s
 }
 
 struct sq_book *sq_book_product(const struct sq_book *book, const struct sq_book *rhs) {
-	struct sq_book *result = sq_book_allocate(book->length + rhs->length);
+	struct sq_book *result = sq_book_allocate(book->length * rhs->length);
 
 	for (unsigned i = 0; i < book->length; ++i)
 		for (unsigned j = 0; j < rhs->length; ++j) {
 			struct sq_book *new = sq_book_allocate(2);
 			new->pages[new->length++] = sq_value_clone(book->pages[i]);
-			new->pages[new->length++] = sq_value_clone(rhs->pages[i]);
+			new->pages[new->length++] = sq_value_clone(rhs->pages[j]);
 			result->pages[result->length++] = sq_value_new(new);
 		}
 
@@ -222,6 +236,14 @@ struct sq_book *sq_book_select(const struct sq_book *book, const struct sq_journ
 	return result;
 }
 
+size_t sq_book_index_of(const struct sq_book *book, sq_value value) {
+	for (unsigned i = 0; i < book->length; ++i)
+		if (sq_value_eql(book->pages[i], value))
+			return i + 1;
+
+	return 0;
+}
+
 sq_value sq_book_reduce(const struct sq_book *book, const struct sq_journey *func) {
 	if (!book->length) return SQ_NI;
 	sq_value acc[2] = { sq_value_clone(book->pages[0]) };
@@ -32,9 +32,9 @@ void sq_codex_dump(FILE *out, const struct sq_codex *codex) {
 	for (unsigned i = 0; i < codex->length; ++i) {
 		if (i) fprintf(out, ", ");
 
-		sq_value_dump(codex->pages[i].key);
+		sq_value_dump_to(out, codex->pages[i].key);
 		fprintf(out, ": ");
-		sq_value_dump(codex->pages[i].value);
+		sq_value_dump_to(out, codex->pages[i].value);
 	}
 
 	putc(')', out);
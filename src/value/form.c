@@ -236,13 +236,19 @@ sq_value sq_imitation_get_attr(struct sq_imitation *imitation, const char *name)
 	if (matter != NULL)
 		return sq_value_clone(*matter);
 
+	// essences are shared by every imitation of a form, so they're visible from each of them.
+	struct sq_essence *essence = sq_form_lookup_essence(imitation->form, name);
+	if (essence != NULL)
+		return sq_value_clone(essence->value);
+
 	return SQ_UNDEFINED;
 }
 
 bool sq_imitation_set_attr(struct sq_imitation *imitation, const char *attr, sq_value value) {
 	int index = sq_imitation_lookup_matter_index(imitation, attr);
 
-	if (index < 0) return false;
+	if (index < 0)
+		return sq_form_set_attr(imitation->form, attr, value);
 
 	if (imitation->form->matter[index].genus != SQ_UNDEFINED && !sq_value_matches(imitation->form->matter[index].genus, value))
 		sq_throw("matter didnt match!");
@@ -272,7 +278,7 @@ void sq_imitation_dump(FILE *out, const struct sq_imitation *imitation) {
 			fprintf(out, ", ");
 
 		fprintf(out, "%s=", imitation->form->matter[i].name);
-		sq_value_dump(imitation->matter[i]);
+		sq_value_dump_to(out, imitation->matter[i]);
 	}
 
 	if (!imitation->form->nmatter)
@@ -25,6 +25,7 @@ static void deallocate_pattern(struct sq_journey_pattern *pattern) {
 
 	free(pattern->pargv);
 	free(pattern->kwargv);
+	free(pattern->captures);
 	free(pattern->code.consts);
 	free(pattern->code.bytecode);
 }
@@ -32,6 +33,16 @@ static void deallocate_pattern(struct sq_journey_pattern *pattern) {
 void sq_journey_deallocate(struct sq_journey *journey) {
 	assert(!journey->refcount);
 
+	// closures only own their captures; everything else belongs to the journey they came from.
+	if (journey->captures != NULL) {
+		for (unsigned i = 0; i < journey->ncaptures; ++i)
+			sq_value_free(journey->captures[i]);
+
+		free(journey->captures);
+		free(journey);
+		return;
+	}
+
 	for (unsigned i = 0; i < journey->npatterns; ++i)
 		deallocate_pattern(&journey->patterns[i]);
 
@@ -53,6 +64,29 @@ struct sq_stackframe {
 
 static sq_value run_stackframe(struct sq_stackframe *stackframe);
 
+static bool argument_matches_genus(
+	struct sq_stackframe *sf,
+	const struct sq_journey_argument *argument,
+	sq_value value
+) {
+	if (argument->genus_start < 0)
+		return true;
+
+	sf->ip = argument->genus_start;
+	sq_value genus = run_stackframe(sf);
+
+	bool matches = sq_value_matches(genus, value);
+
+	// if there's only one pattern, a mismatch is almost certainly a mistake, so
+	// give a more helpful error than "no patterns match".
+	if (!matches && sf->journey->npatterns == 1)
+		sq_throw("argument '%s' for '%s' must be a %s, not a %s",
+			argument->name, sf->journey->name, sq_value_to_text(genus)->ptr, sq_value_typename(value));
+
+	sq_value_free(genus);
+	return matches;
+}
+
 static int assign_positional_arguments(
 	struct sq_stackframe *sf,
 	const struct sq_journey_pattern *pattern,
@@ -93,18 +127,9 @@ static int assign_positional_arguments(
 	// make sure all the non-splat parameters match
 	assert(i == pattern->pargc);
 
-	for (unsigned j = 0; j < i; ++j) {
-		if (pattern->pargv[j].genus_start < 0)
-			continue;
-
-		sf->ip = pattern->pargv[j].genus_start;
-		sq_value genus = run_stackframe(sf);
-
-		bool matches = sq_value_matches(genus, sf->locals[j]);
-		sq_value_free(genus);
-		if (!matches)
+	for (unsigned j = 0; j < i; ++j)
+		if (!argument_matches_genus(sf, &pattern->pargv[j], sf->locals[j]))
 			return -1;
-	}
 
 	if (pattern->splat && splat == NULL)
 		splat = sq_book_allocate(0);
@@ -117,6 +142,94 @@ static int assign_positional_arguments(
 	return i;
 }
 
+static int assign_keyword_arguments(
+	struct sq_stackframe *sf,
+	const struct sq_journey_pattern *pattern,
+	struct sq_args *args,
+	unsigned index
+) {
+	bool used[args->kwargc + 1]; // `+1` so we never have a zero-length array.
+	memset(used, 0, sizeof(used));
+
+	for (unsigned i = 0; i < pattern->kwargc; ++i, ++index) {
+		unsigned j;
+
+		for (j = 0; j < args->kwargc; ++j)
+			if (!used[j] && !strcmp(pattern->kwargv[i].name, args->kwargv[j].name))
+				break;
+
+		if (j != args->kwargc) {
+			used[j] = true;
+			sf->locals[index] = sq_value_clone(args->kwargv[j].value);
+		} else if (pattern->kwargv[i].default_start < 0) {
+			return -1; // the keyword argument wasn't supplied, and there's no default.
+		} else {
+			sf->ip = pattern->kwargv[i].default_start;
+			sf->locals[index] = run_stackframe(sf);
+		}
+
+		if (!argument_matches_genus(sf, &pattern->kwargv[i], sf->locals[index]))
+			return -1;
+	}
+
+	// any leftover keyword arguments are either put into the splatsplat or are an error.
+	struct sq_codex *splatsplat = NULL;
+
+	for (unsigned j = 0; j < args->kwargc; ++j) {
+		if (used[j])
+			continue;
+
+		if (!pattern->splatsplat)
+			return -1; // unknown keyword argument given, and no splatsplat provided.
+
+		if (splatsplat == NULL)
+			splatsplat = sq_codex_allocate(args->kwargc - j);
+
+		sq_codex_index_assign(
+			splatsplat,
+			sq_value_new(sq_text_new(strdup(args->kwargv[j].name))),
+			sq_value_clone(args->kwargv[j].value)
+		);
+	}
+
+	if (pattern->splatsplat)
+		sf->locals[index++] = sq_value_new(splatsplat == NULL ? sq_codex_allocate(0) : splatsplat);
+
+	return index;
+}
+
+// Ensures `value` matches `genus`, converting it to builtin genera if it doesn't.
+static sq_value coerce_return_value(const struct sq_journey *journey, sq_value genus, sq_value value) {
+	if (sq_value_matches(genus, value))
+		return value;
+
+	sq_value coerced = SQ_UNDEFINED;
+
+	if (sq_value_is_text(genus)) {
+		const char *name = sq_value_as_text(genus)->ptr;
+
+		if (!strcmp(name, "Numeral"))
+			coerced = sq_value_new(sq_value_to_numeral(value));
+		else if (!strcmp(name, "Decimal"))
+			coerced = sq_value_new(sq_value_to_decimal(value));
+		else if (!strcmp(name, "Text"))
+			coerced = sq_value_new(sq_value_to_text(value));
+		else if (!strcmp(name, "Veracity"))
+			coerced = sq_value_new(sq_value_to_veracity(value));
+		else if (!strcmp(name, "Book"))
+			coerced = sq_value_new(sq_value_to_book(value));
+		else if (!strcmp(name, "Codex"))
+			coerced = sq_value_new(sq_value_to_codex(value));
+	}
+
+	if (coerced == SQ_UNDEFINED)
+		sq_throw("'%s' must reward a %s, not a %s",
+			journey->name, sq_value_to_text(genus)->ptr, sq_value_typename(value));
+
+	sq_value_free(value);
+	return coerced;
+}
+
 static sq_value try_run_pattern(
 	const struct sq_journey *journey,
 	const struct sq_journey_pattern *pattern,
@@ -130,12 +243,18 @@ static sq_value try_run_pattern(
 
 	sq_value result = SQ_UNDEFINED;
 
+	if (journey->captures != NULL) {
+		for (unsigned i = 0; i < pattern->ncaptures; ++i)
+			sf.locals[pattern->captures[i].local] = sq_value_clone(journey->captures[pattern->captures[i].index]);
+	}
+
 	int positional_argument_stop_index = assign_positional_arguments(&sf, pattern, args);
 
 	if (positional_argument_stop_index < 0)
 		goto free_and_return;
 
-	// todo: handle keyword arguments
+	if (assign_keyword_arguments(&sf, pattern, args, positional_argument_stop_index) < 0)
+		goto free_and_return;
 
 	// ie we have a condition
 	if (0 <= pattern->condition_start) {
@@ -149,6 +268,13 @@ static sq_value try_run_pattern(
 	sf.ip = pattern->start_index;
 	result = run_stackframe(&sf);
 
+	if (0 <= pattern->return_genus_start) {
+		sf.ip = pattern->return_genus_start;
+		sq_value genus = run_stackframe(&sf);
+		result = coerce_return_value(journey, genus, result);
+		sq_value_free(genus);
+	}
+
 free_and_return:
 
 	for (unsigned i = 0; i < pattern->code.nlocals; ++i)
@@ -158,14 +284,103 @@ free_and_return:
 	return result;
 }
 
+// Throws if `pattern` can never accept `argc` positional arguments, so that journeys with only one
+// pattern give a more helpful message than "no patterns match".
+static void check_argument_count(const struct sq_journey *journey, const struct sq_journey_pattern *pattern, unsigned argc) {
+	unsigned min = pattern->pargc, max = pattern->pargc;
+
+	// only trailing arguments can have defaults.
+	while (min && 0 <= pattern->pargv[min - 1].default_start)
+		--min;
+
+	if (pattern->splat) {
+		if (min <= argc)
+			return;
+
+		sq_throw("argument mismatch for '%s': expected at least %u, given %u", journey->name, min, argc);
+	}
+
+	if (min <= argc && argc <= max)
+		return;
+
+	if (min == max)
+		sq_throw("argument mismatch for '%s': expected %u, given %u", journey->name, min, argc);
+
+	sq_throw("argument mismatch for '%s': expected %u to %u, given %u", journey->name, min, max, argc);
+}
+
+unsigned sq_journey_max_depth = SQ_JOURNEY_MAX_DEPTH;
+static unsigned journey_depth, journey_stack_cap;
+static const char **journey_stack;
+
+const char *const *sq_journey_stack(unsigned *depth) {
+	*depth = journey_depth;
+	return journey_stack;
+}
+
+// set by `SQ_OC_TAILCALL` for `sq_journey_run` to call once the current pattern's finished.
+static struct { sq_value journey; struct sq_args args; } tail_call = { .journey = SQ_UNDEFINED };
+
+static void free_tail_call(sq_value journey, struct sq_args args) {
+	for (unsigned i = 0; i < args.pargc; ++i)
+		sq_value_free(args.pargv[i]);
+
+	free(args.pargv);
+	sq_value_free(journey);
+}
+
+void sq_journey_reset_stack(void) {
+	journey_depth = 0;
+
+	if (tail_call.journey != SQ_UNDEFINED) {
+		free_tail_call(tail_call.journey, tail_call.args);
+		tail_call.journey = SQ_UNDEFINED;
+	}
+}
+
 sq_value sq_journey_run(const struct sq_journey *journey, struct sq_args args) {
-	sq_value result;
+	sq_value result, tail_called = SQ_UNDEFINED; // the journey we're running for a tail call, if any.
 
-	for (unsigned i = 0; i < journey->npatterns; ++i)
-		if ((result = try_run_pattern(journey, &journey->patterns[i], &args)) != SQ_UNDEFINED)
-			return result;
+	if (sq_journey_max_depth <= journey_depth)
+		sq_throw("stack overflow: journeys nested more than %u deep", sq_journey_max_depth);
+
+	if (journey_depth == journey_stack_cap) {
+		journey_stack_cap = journey_stack_cap ? journey_stack_cap * 2 : 64;
+		journey_stack = xrealloc(journey_stack, sizeof_array(const char *, journey_stack_cap));
+	}
+
+	journey_stack[journey_depth++] = journey->name;
+
+call_journey:
+
+	for (unsigned i = 0; i < journey->npatterns; ++i) {
+		if ((result = try_run_pattern(journey, &journey->patterns[i], &args)) == SQ_UNDEFINED)
+			continue;
+
+		if (tail_called != SQ_UNDEFINED)
+			free_tail_call(tail_called, args);
+
+		// the pattern ended with a tail call, so run it in place of this journey.
+		if (tail_call.journey != SQ_UNDEFINED) {
+			tail_called = tail_call.journey;
+			args = tail_call.args;
+			tail_call.journey = SQ_UNDEFINED;
+
+			journey = sq_value_as_journey(tail_called);
+			journey_stack[journey_depth - 1] = journey->name;
+			goto call_journey;
+		}
+
+		--journey_depth;
+		return result;
+	}
+
+	--journey_depth;
 
 	// whelp, no pattern matched. exception time!
+	if (journey->npatterns == 1)
+		check_argument_count(journey, &journey->patterns[0], args.pargc);
+
 	sq_throw("no patterns match for '%s'", journey->name);
 }
 
@@ -245,12 +460,18 @@ static unsigned interrupt_operands(enum sq_interrupt interrupt) {
 	case SQ_INT_TOBOOK:
 	case SQ_INT_TOCODEX:
 	case SQ_INT_KINDOF:
+	case SQ_INT_KINDNAME:
 	case SQ_INT_PRINT:
 	case SQ_INT_PRINTLN:
 	case SQ_INT_DUMP:
 	case SQ_INT_SYSTEM:
 	case SQ_INT_EXIT:
 	case SQ_INT_LENGTH:
+	case SQ_INT_UPPER:
+	case SQ_INT_LOWER:
+	case SQ_INT_TRIM:
+	case SQ_INT_TRIM_START:
+	case SQ_INT_TRIM_END:
 	case SQ_INT_ARABIC:
 	case SQ_INT_ROMAN:
 		return 1;
@@ -260,11 +481,23 @@ static unsigned interrupt_operands(enum sq_interrupt interrupt) {
 
 	case SQ_INT_SUBSTR:
 	case SQ_INT_ARRAY_INSERT:
+	case SQ_INT_ARRAY_UNPACK:
+	case SQ_INT_REPLACE:
 		return 3;
 
 	case SQ_INT_ARRAY_DELETE:
+	case SQ_INT_RANGE_NEW:
+	case SQ_INT_SPLIT:
+	case SQ_INT_JOIN:
+	case SQ_INT_STARTS_WITH:
+	case SQ_INT_ENDS_WITH:
+	case SQ_INT_ARRAY_CONTAINS:
+	case SQ_INT_ARRAY_INDEX_OF:
 		return 2;
 
+	case SQ_INT_JOURNEY_CAPTURE:
+		return 1;
+
 	case SQ_INT_CODEX_NEW:
 	case SQ_INT_BOOK_NEW:
 		return 0;
@@ -324,12 +557,21 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 		set_next_local(sf, sq_value_clone(sq_value_genus(operands[0])));
 		return;
 
+	// [A,DST] DST <- name of A's genus, as a text
+	case SQ_INT_KINDNAME:
+		set_next_local(sf, sq_value_new(sq_text_new(strdup(
+			sq_value_is_imitation(operands[0])
+				? sq_value_as_imitation(operands[0])->form->name
+				: sq_value_typename(operands[0])
+		))));
+		return;
+
 	// [A,DST] Print `A`, DST <- ni
 	case SQ_INT_PRINT:
 		text = sq_value_to_text(operands[0]);
-		if (!fputs(text->ptr, stdout))
+		if (fputs(text->ptr, sf->journey->program->output) == EOF)
 			sq_throw_io("proclaimnl");
-		fflush(stdout);
+		fflush(sf->journey->program->output);
 
 		sq_text_free(text);
 		set_next_local(sf, SQ_NI);
@@ -338,9 +580,10 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 	// [A,DST] Print `A` with a newline, DST <- ni
 	case SQ_INT_PRINTLN:
 		text = sq_value_to_text(operands[0]);
-		if (!puts(text->ptr))
+		if (fputs(text->ptr, sf->journey->program->output) == EOF
+			|| putc('\n', sf->journey->program->output) == EOF)
 			sq_throw_io("proclaim");
-		fflush(stdout);
+		fflush(sf->journey->program->output);
 
 		sq_text_free(text);
 		set_next_local(sf, SQ_NI);
@@ -348,21 +591,20 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 
 	// [A,DST] Dumps out `A`, DST <- A
 	case SQ_INT_DUMP:
-		sq_value_dump(operands[0]);
-		putchar('\n');
+		sq_value_dump_to(sf->journey->program->output, operands[0]);
+		putc('\n', sf->journey->program->output);
 		set_next_local(sf, sq_value_clone(operands[0]));
 		return;
 
-	// [DST] DST <- next line from stdin
+	// [DST] DST <- next line from the program's input, or ni at the end of it
 	case SQ_INT_PROMPT: {
 		char *line = NULL;
-		size_t cap, length;
+		size_t cap = 0, length;
 
-		if ((length = getline(&line, &cap, stdin)) == (size_t) -1) {
+		if ((length = getline(&line, &cap, sf->journey->program->input)) == (size_t) -1) {
 			free(line);
-			line = strdup("");
-			cap = 0;
-			length = 0;
+			set_next_local(sf, SQ_NI);
+			break;
 		}
 
 		if (length && line[length-1] == '\n') {
@@ -417,7 +659,7 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 		return;
 	}
 
-	// [CODE] Exits with the given code.
+	// [CODE,DST] Exits with the given code; DST is never set.
 	case SQ_INT_EXIT:
 		exit(sq_value_to_numeral(operands[0]));
 
@@ -432,17 +674,11 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 	case SQ_INT_SUBSTR: {
 		text = sq_value_to_text(operands[0]);
 		sq_numeral start = sq_value_to_numeral(operands[1]);
-		if (!start--)
+		if (!start)
 			sq_throw("cannot index by N.");
-		sq_numeral count = sq_value_to_numeral(operands[2]);
-		struct sq_text *result;
 
-		if (!*text->ptr || start >= text->length) 
-			result = sq_text_new2(strdup(""), 0);
-		else if (start + count < text->length)
-			result = sq_text_new2(strndup(text->ptr + start, count), count);
-		else
-			result = sq_text_new(strdup(text->ptr + start)); // todo: we know the length
+		struct sq_text *result = sq_text_substr(text, start, sq_value_to_numeral(operands[2]));
+		sq_text_free(text);
 
 		set_next_local(sf, sq_value_new(result));
 		return;
@@ -453,15 +689,99 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 		set_next_local(sf, sq_value_new((sq_numeral) sq_value_length(operands[0])));
 		return;
 
+	// [A,DST] DST <- A.to_text().upper()
+	case SQ_INT_UPPER:
+		text = sq_value_to_text(operands[0]);
+		set_next_local(sf, sq_value_new(sq_text_upper(text)));
+		sq_text_free(text);
+		return;
+
+	// [A,DST] DST <- A.to_text().lower()
+	case SQ_INT_LOWER:
+		text = sq_value_to_text(operands[0]);
+		set_next_local(sf, sq_value_new(sq_text_lower(text)));
+		sq_text_free(text);
+		return;
+
+	// [A,DST] DST <- A.to_text() without leading and/or trailing whitespace
+	case SQ_INT_TRIM:
+	case SQ_INT_TRIM_START:
+	case SQ_INT_TRIM_END:
+		text = sq_value_to_text(operands[0]);
+		set_next_local(sf, sq_value_new(sq_text_trim(text, interrupt != SQ_INT_TRIM_END, interrupt != SQ_INT_TRIM_START)));
+		sq_text_free(text);
+		return;
+
+	// [A,B,DST] DST <- A.to_text() split on B.to_text(), or into characters if it's empty
+	case SQ_INT_SPLIT: {
+		text = sq_value_to_text(operands[0]);
+		struct sq_text *sep = sq_value_to_text(operands[1]);
+
+		if (sep->length) {
+			set_next_local(sf, sq_value_new(sq_text_split(text, sep)));
+		} else {
+			sq_value value = sq_value_new(text);
+			set_next_local(sf, sq_value_new(sq_value_to_book(value)));
+		}
+
+		sq_text_free(text);
+		sq_text_free(sep);
+		return;
+	}
+
+	// [A,B,DST] DST <- whether A.to_text() starts (or ends) with B.to_text()
+	case SQ_INT_STARTS_WITH:
+	case SQ_INT_ENDS_WITH: {
+		text = sq_value_to_text(operands[0]);
+		struct sq_text *affix = sq_value_to_text(operands[1]);
 
-	// [N,...,DST] DST <- N key-value pairs.
+		set_next_local(sf, sq_value_new((sq_veracity) (interrupt == SQ_INT_STARTS_WITH
+			? sq_text_starts_with(text, affix)
+			: sq_text_ends_with(text, affix))));
+
+		sq_text_free(text);
+		sq_text_free(affix);
+		return;
+	}
+
+	// [A,B,C,DST] DST <- A.to_text() with each B.to_text() replaced by C.to_text()
+	case SQ_INT_REPLACE: {
+		struct sq_text *from = sq_value_to_text(operands[1]);
+		if (!from->length) {
+			sq_text_free(from);
+			sq_throw("cannot replace an empty text");
+		}
+
+		struct sq_text *to = sq_value_to_text(operands[2]);
+		text = sq_value_to_text(operands[0]);
+		set_next_local(sf, sq_value_new(sq_text_replace(text, from, to)));
+
+		sq_text_free(text);
+		sq_text_free(from);
+		sq_text_free(to);
+		return;
+	}
+
+	// [A,B,DST] DST <- A's pages joined with B.to_text()
+	case SQ_INT_JOIN: {
+		if (!sq_value_is_book(operands[0]))
+			sq_throw("can only join books, not %s", sq_value_typename(operands[0]));
+
+		text = sq_value_to_text(operands[1]);
+		set_next_local(sf, sq_value_new(sq_book_join(sq_value_as_book(operands[0]), text)));
+		sq_text_free(text);
+		return;
+	}
+
+
+	// [N,...,DST] DST <- N key-value pairs. Later pairs overwrite earlier ones with the same key.
 	case SQ_INT_CODEX_NEW: {
 		unsigned amnt = next_count(sf);
 		struct sq_codex *codex = sq_codex_allocate(amnt);
 
-		for (; codex->length < amnt; ++codex->length) {
-			codex->pages[codex->length].key = sq_value_clone(*next_local(sf));
-			codex->pages[codex->length].value = sq_value_clone(*next_local(sf));
+		for (unsigned i = 0; i < amnt; ++i) {
+			sq_value key = sq_value_clone(*next_local(sf));
+			sq_codex_index_assign(codex, key, sq_value_clone(*next_local(sf)));
 		}
 
 		set_next_local(sf, sq_value_new(codex));
@@ -506,6 +826,71 @@ static void handle_interrupt(struct sq_stackframe *sf) {
 		return;
 	}
 
+	// [A,B,DST] DST <- whether any page of A equals B
+	// [A,B,DST] DST <- the index of the first page of A equal to B, or ni
+	case SQ_INT_ARRAY_CONTAINS:
+	case SQ_INT_ARRAY_INDEX_OF: {
+		if (interrupt == SQ_INT_ARRAY_CONTAINS && sq_value_is_text(operands[0])) {
+			text = sq_value_to_text(operands[1]);
+			set_next_local(sf, sq_value_new((sq_veracity) (strstr(sq_value_as_text(operands[0])->ptr, text->ptr) != NULL)));
+			sq_text_free(text);
+			return;
+		}
+
+		if (!sq_value_is_book(operands[0]))
+			sq_throw("can only search books, not %s", sq_value_typename(operands[0]));
+
+		size_t index = sq_book_index_of(sq_value_as_book(operands[0]), operands[1]);
+
+		if (interrupt == SQ_INT_ARRAY_CONTAINS)
+			set_next_local(sf, sq_value_new((sq_veracity) (index != 0)));
+		else
+			set_next_local(sf, index ? sq_value_new((sq_numeral) index) : SQ_NI);
+		return;
+	}
+
+	// [A,N,R,DST] Throws unless A has N pages (or at least N if R); DST <- the rest
+	case SQ_INT_ARRAY_UNPACK: {
+		if (!sq_value_is_book(operands[0]))
+			sq_throw("can only destructure books, not %s", sq_value_typename(operands[0]));
+
+		struct sq_book *book = sq_value_as_book(operands[0]);
+		unsigned count = sq_value_as_numeral(operands[1]);
+		bool has_rest = sq_value_to_veracity(operands[2]);
+
+		if (has_rest ? book->length < count : book->length != count)
+			sq_throw("cannot destructure a book of %zu pages into %s%u names",
+				book->length, has_rest ? "at least " : "", count);
+
+		struct sq_book *rest = sq_book_allocate(book->length - count);
+		for (unsigned i = count; i < book->length; ++i)
+			rest->pages[rest->length++] = sq_value_clone(book->pages[i]);
+
+		set_next_local(sf, sq_value_new(rest));
+		return;
+	}
+
+	// [A,B,DST] DST <- A..B
+	case SQ_INT_RANGE_NEW:
+		set_next_local(sf, sq_value_new(sq_range_new(sq_value_to_numeral(operands[0]), sq_value_to_numeral(operands[1]))));
+		return;
+
+	// [J,N,...,DST] DST <- a closure of J capturing N values.
+	case SQ_INT_JOURNEY_CAPTURE: {
+		struct sq_journey *closure = xmalloc(sizeof(struct sq_journey));
+
+		*closure = *sq_value_as_journey(operands[0]);
+		closure->refcount = 1;
+		closure->ncaptures = next_count(sf);
+		closure->captures = xmalloc(sizeof_array(sq_value, closure->ncaptures));
+
+		for (unsigned i = 0; i < closure->ncaptures; ++i)
+			closure->captures[i] = sq_value_clone(*next_local(sf));
+
+		set_next_local(sf, sq_value_new(closure));
+		return;
+	}
+
 
 	// [A,DST] DST <- A.to_numeral().arabic()
 	case SQ_INT_ARABIC:
@@ -609,9 +994,14 @@ static unsigned normal_operands(enum sq_opcode opcode) {
 		case SQ_OC_NOT:
 		case SQ_OC_NEG:
 		case SQ_OC_CALL:
+		case SQ_OC_KWCALL:
+		case SQ_OC_TAILCALL:
 		case SQ_OC_GSTORE:
 		case SQ_OC_ILOAD:
 		case SQ_OC_RETURN:
+		case SQ_OC_THROW:
+		case SQ_OC_ITER_START:
+		case SQ_OC_ITER_NEXT:
 			return 1;
 
 		case SQ_OC_EQL:
@@ -627,6 +1017,11 @@ static unsigned normal_operands(enum sq_opcode opcode) {
 		case SQ_OC_DIV:
 		case SQ_OC_MOD:
 		case SQ_OC_POW:
+		case SQ_OC_BAND:
+		case SQ_OC_BOR:
+		case SQ_OC_BXOR:
+		case SQ_OC_SHL:
+		case SQ_OC_SHR:
 		case SQ_OC_MATCHES:
 		case SQ_OC_INDEX:
 		case SQ_OC_ISTORE:
@@ -642,14 +1037,694 @@ static unsigned normal_operands(enum sq_opcode opcode) {
 	}
 }
 
+static void disassemble_constant(FILE *out, const struct sq_codeblock *code, unsigned index) {
+	fprintf(out, " #%u", index);
+
+	if (index < code->nconsts) {
+		fputs(" (", out);
+		sq_value_dump_to(out, code->consts[index]);
+		fputc(')', out);
+	} else {
+		fputs(" (<out of bounds>)", out);
+	}
+}
+
+static void disassemble_global(FILE *out, unsigned index, const char *const *global_names) {
+	if (global_names)
+		fprintf(out, " @%u (%s)", index, global_names[index]);
+	else
+		fprintf(out, " @%u", index);
+}
+
+void sq_codeblock_disassemble(FILE *out, const struct sq_codeblock *code, const char *const *global_names) {
+	unsigned ip = 0, arity, amnt, end;
+	enum sq_opcode opcode;
+	enum sq_interrupt interrupt;
+
+#define NEXT() (code->bytecode[ip++])
+#define LOCAL() (fprintf(out, " $%u", NEXT().index))
+#define DST() (fprintf(out, " -> $%u", NEXT().index))
+#define INDEX() (fprintf(out, " %u", NEXT().index))
+#define COUNT() (fprintf(out, " %u", amnt = NEXT().count))
+
+	while (ip < code->codelen) {
+		fprintf(out, "%5u: ", ip);
+		opcode = NEXT().opcode;
+		fprintf(out, "%-14s", sq_opcode_repr(opcode) + sizeof("SQ_OC_") - 1);
+
+		arity = normal_operands(opcode);
+		for (unsigned i = 0; i < arity; ++i)
+			LOCAL();
+
+		switch (opcode) {
+		case SQ_OC_NOOP:
+		case SQ_OC_POPTRYCATCH:
+		case SQ_OC_UNDEFINED:
+		case SQ_OC_RETURN:
+		case SQ_OC_THROW:
+		case SQ_OC_INDEX_ASSIGN:
+			break;
+
+		case SQ_OC_INT:
+			interrupt = NEXT().interrupt;
+			fprintf(out, " %s", sq_interrupt_repr(interrupt) + sizeof("SQ_INT_") - 1);
+
+			for (unsigned i = 0; i < interrupt_operands(interrupt); ++i)
+				LOCAL();
+
+			if (interrupt == SQ_INT_BOOK_NEW || interrupt == SQ_INT_CODEX_NEW || interrupt == SQ_INT_JOURNEY_CAPTURE) {
+				COUNT();
+				if (interrupt == SQ_INT_CODEX_NEW)
+					amnt *= 2;
+
+				for (unsigned i = 0; i < amnt; ++i)
+					LOCAL();
+			}
+
+			DST();
+			break;
+
+		case SQ_OC_JMP:
+		case SQ_OC_JMP_FALSE:
+		case SQ_OC_JMP_TRUE:
+#ifndef SQ_NMOON_JOKE
+		case SQ_OC_WERE_JMP:
+#endif /* SQ_NMOON_JOKE */
+			fprintf(out, " => %u", NEXT().index);
+			break;
+
+		case SQ_OC_COMEFROM:
+			amnt = NEXT().count;
+			end = NEXT().index;
+			fprintf(out, " %u (end => %u)", amnt, end);
+
+			for (unsigned i = 0; i < amnt; ++i)
+				fprintf(out, " => %u", code->bytecode[ip + i].index);
+
+			ip = end;
+			break;
+
+		case SQ_OC_TRYCATCH:
+			fprintf(out, " => %u", NEXT().index);
+			DST();
+			break;
+
+		case SQ_OC_ITER_NEXT:
+			LOCAL();
+			DST();
+			fprintf(out, " => %u", NEXT().index);
+			break;
+
+		case SQ_OC_CALL:
+			COUNT();
+			for (unsigned i = 0; i < amnt; ++i)
+				LOCAL();
+			DST();
+			break;
+
+		case SQ_OC_TAILCALL:
+			COUNT();
+			for (unsigned i = 0; i < amnt; ++i)
+				LOCAL();
+			break;
+
+		case SQ_OC_KWCALL: {
+			unsigned kwamnt;
+
+			COUNT();
+			fprintf(out, " %u", kwamnt = NEXT().count);
+			for (unsigned i = 0; i < amnt; ++i)
+				LOCAL();
+
+			for (unsigned i = 0; i < kwamnt; ++i) {
+				disassemble_constant(out, code, NEXT().index);
+				fputc(':', out);
+				LOCAL();
+			}
+
+			DST();
+			break;
+		}
+
+		case SQ_OC_CLOAD:
+			disassemble_constant(out, code, NEXT().index);
+			DST();
+			break;
+
+		case SQ_OC_GLOAD:
+			disassemble_global(out, NEXT().index, global_names);
+			DST();
+			break;
+
+		case SQ_OC_GSTORE:
+			disassemble_global(out, NEXT().index, global_names);
+			break;
+
+		case SQ_OC_ILOAD:
+			disassemble_constant(out, code, NEXT().index);
+			DST();
+			break;
+
+		case SQ_OC_ISTORE:
+			disassemble_constant(out, code, NEXT().index);
+			break;
+
+		case SQ_OC_FEGENUS_STORE:
+		case SQ_OC_FMGENUS_STORE:
+			INDEX();
+			break;
+
+		default:
+			DST();
+		}
+
+		fputc('\n', out);
+	}
+
+#undef NEXT
+#undef LOCAL
+#undef DST
+#undef INDEX
+#undef COUNT
+}
+
+static bool is_valid_interrupt(enum sq_interrupt interrupt) {
+	switch (interrupt) {
+	case SQ_INT_TONUMERAL: case SQ_INT_TOTEXT: case SQ_INT_TOVERACITY:
+	case SQ_INT_TOBOOK: case SQ_INT_TOCODEX: case SQ_INT_KINDOF: case SQ_INT_KINDNAME:
+	case SQ_INT_PRINT: case SQ_INT_PRINTLN: case SQ_INT_DUMP: case SQ_INT_PROMPT:
+	case SQ_INT_SYSTEM: case SQ_INT_EXIT: case SQ_INT_RANDOM:
+	case SQ_INT_SUBSTR: case SQ_INT_LENGTH: case SQ_INT_UPPER: case SQ_INT_LOWER:
+	case SQ_INT_TRIM: case SQ_INT_TRIM_START: case SQ_INT_TRIM_END: case SQ_INT_SPLIT: case SQ_INT_JOIN:
+	case SQ_INT_STARTS_WITH: case SQ_INT_ENDS_WITH: case SQ_INT_REPLACE:
+	case SQ_INT_CODEX_NEW: case SQ_INT_BOOK_NEW: case SQ_INT_ARRAY_INSERT: case SQ_INT_ARRAY_DELETE:
+	case SQ_INT_RANGE_NEW: case SQ_INT_JOURNEY_CAPTURE: case SQ_INT_ARRAY_CONTAINS: case SQ_INT_ARRAY_INDEX_OF:
+	case SQ_INT_ARRAY_UNPACK:
+	case SQ_INT_ARABIC: case SQ_INT_ROMAN:
+	case SQ_INT_FOPEN: case SQ_INT_FCLOSE: case SQ_INT_FREAD: case SQ_INT_FREADALL:
+	case SQ_INT_FWRITE: case SQ_INT_FTELL: case SQ_INT_FSEEK:
+	case SQ_INT_ASCII:
+		return true;
+
+	default:
+		return false;
+	}
+}
+
+// Checks each instruction of `code` on its own, marking where they start in `starts` and recording
+// every jump target (along with the offset of the instruction it's from) in `targets`.
+static const char *verify_instructions(
+	const struct sq_codeblock *code,
+	unsigned *offset,
+	bool *starts,
+	unsigned (*targets)[2],
+	unsigned *ntargets
+) {
+	unsigned ip = 0, amnt, kwamnt, end;
+	union sq_bytecode bytecode;
+
+#define READ() do { \
+		if (code->codelen <= ip) return "truncated instruction"; \
+		bytecode = code->bytecode[ip++]; \
+	} while(0)
+#define LOCAL() do { \
+		READ(); \
+		if (code->nlocals <= bytecode.index) return "local out of bounds"; \
+	} while(0)
+#define CONSTANT() do { \
+		READ(); \
+		if (code->nconsts <= bytecode.index) return "constant out of bounds"; \
+	} while(0)
+#define TEXT_CONSTANT() do { \
+		CONSTANT(); \
+		if (!sq_value_is_text(code->consts[bytecode.index])) return "constant must be a text"; \
+	} while(0)
+#define TARGET() do { \
+		READ(); \
+		if (code->codelen < bytecode.index) return "jump target out of bounds"; \
+		targets[*ntargets][0] = *offset; \
+		targets[(*ntargets)++][1] = bytecode.index; \
+	} while(0)
+
+	while (ip < code->codelen) {
+		*offset = ip;
+		starts[ip] = true;
+		READ();
+		enum sq_opcode opcode = bytecode.opcode;
+
+		for (unsigned i = 0; i < normal_operands(opcode); ++i)
+			LOCAL();
+
+		switch (opcode) {
+		case SQ_OC_NOOP:
+		case SQ_OC_POPTRYCATCH:
+		case SQ_OC_RETURN:
+		case SQ_OC_THROW:
+		case SQ_OC_INDEX_ASSIGN:
+			break;
+
+		case SQ_OC_MOV:
+		case SQ_OC_NOT:
+		case SQ_OC_NEG:
+		case SQ_OC_EQL:
+		case SQ_OC_NEQ:
+		case SQ_OC_LTH:
+		case SQ_OC_GTH:
+		case SQ_OC_LEQ:
+		case SQ_OC_GEQ:
+		case SQ_OC_CMP:
+		case SQ_OC_ADD:
+		case SQ_OC_SUB:
+		case SQ_OC_MUL:
+		case SQ_OC_DIV:
+		case SQ_OC_MOD:
+		case SQ_OC_POW:
+		case SQ_OC_BAND:
+		case SQ_OC_BOR:
+		case SQ_OC_BXOR:
+		case SQ_OC_SHL:
+		case SQ_OC_SHR:
+		case SQ_OC_INDEX:
+		case SQ_OC_MATCHES:
+		case SQ_OC_ITER_START:
+			LOCAL();
+			break;
+
+		case SQ_OC_ITER_NEXT:
+			LOCAL();
+			LOCAL();
+			TARGET();
+			break;
+
+		case SQ_OC_INT: {
+			READ();
+			enum sq_interrupt interrupt = bytecode.interrupt;
+
+			if (!is_valid_interrupt(interrupt))
+				return "unknown interrupt";
+
+			for (unsigned i = 0; i < interrupt_operands(interrupt); ++i)
+				LOCAL();
+
+			if (interrupt == SQ_INT_BOOK_NEW || interrupt == SQ_INT_CODEX_NEW || interrupt == SQ_INT_JOURNEY_CAPTURE) {
+				READ();
+				amnt = bytecode.count;
+
+				for (unsigned i = 0; i < amnt; ++i) {
+					LOCAL();
+					if (interrupt == SQ_INT_CODEX_NEW)
+						LOCAL();
+				}
+			}
+
+			LOCAL();
+			break;
+		}
+
+		case SQ_OC_JMP:
+		case SQ_OC_JMP_FALSE:
+		case SQ_OC_JMP_TRUE:
+#ifndef SQ_NMOON_JOKE
+		case SQ_OC_WERE_JMP:
+#endif /* SQ_NMOON_JOKE */
+			TARGET();
+			break;
+
+		case SQ_OC_COMEFROM:
+			READ();
+			amnt = bytecode.count;
+			TARGET();
+			end = bytecode.index;
+
+			if (end < ip + amnt)
+				return "comefrom end overlaps its targets";
+
+			for (unsigned i = 0; i < amnt; ++i)
+				TARGET();
+
+			ip = end;
+			break;
+
+		case SQ_OC_TRYCATCH:
+			TARGET();
+			LOCAL();
+			break;
+
+		case SQ_OC_CALL:
+			READ();
+			amnt = bytecode.count;
+
+			for (unsigned i = 0; i < amnt; ++i)
+				LOCAL();
+
+			LOCAL();
+			break;
+
+		case SQ_OC_TAILCALL:
+			READ();
+			amnt = bytecode.count;
+
+			for (unsigned i = 0; i < amnt; ++i)
+				LOCAL();
+			break;
+
+		case SQ_OC_KWCALL:
+			READ();
+			amnt = bytecode.count;
+			READ();
+			kwamnt = bytecode.count;
+
+			for (unsigned i = 0; i < amnt; ++i)
+				LOCAL();
+
+			for (unsigned i = 0; i < kwamnt; ++i) {
+				TEXT_CONSTANT();
+				LOCAL();
+			}
+
+			LOCAL();
+			break;
+
+		case SQ_OC_CLOAD:
+			CONSTANT();
+			LOCAL();
+			break;
+
+		case SQ_OC_GLOAD:
+			READ(); // globals are only known by the program, so we can't check them.
+			LOCAL();
+			break;
+
+		case SQ_OC_GSTORE:
+			READ();
+			break;
+
+		case SQ_OC_ILOAD:
+			TEXT_CONSTANT();
+			LOCAL();
+			break;
+
+		case SQ_OC_ISTORE:
+			TEXT_CONSTANT();
+			break;
+
+		case SQ_OC_FEGENUS_STORE:
+		case SQ_OC_FMGENUS_STORE:
+			READ();
+			break;
+
+		default:
+			return "unknown opcode";
+		}
+	}
+
+#undef READ
+#undef LOCAL
+#undef CONSTANT
+#undef TEXT_CONSTANT
+#undef TARGET
+
+	return NULL;
+}
+
+const char *sq_codeblock_verify(const struct sq_codeblock *code, unsigned *offset) {
+	// jumping to the very end is fine; it just returns.
+	bool *starts = xcalloc(code->codelen + 1, sizeof(bool));
+	unsigned (*targets)[2] = xmalloc(sizeof_array(unsigned[2], code->codelen + 1));
+	unsigned ntargets = 0;
+	const char *error;
+
+	starts[code->codelen] = true;
+
+	// jumping into the middle of an instruction would read its operands as opcodes.
+	if (!(error = verify_instructions(code, offset, starts, targets, &ntargets))) {
+		for (unsigned i = 0; i < ntargets; ++i) {
+			if (!starts[targets[i][1]]) {
+				*offset = targets[i][0];
+				error = "jump target isn't the start of an instruction";
+				break;
+			}
+		}
+	}
+
+	free(starts);
+	free(targets);
+	return error;
+}
+
+// Returns how many words the instruction at `ip` takes up, including the opcode itself.
+static unsigned instruction_length(const struct sq_codeblock *code, unsigned ip) {
+	enum sq_opcode opcode = code->bytecode[ip].opcode;
+	unsigned length = 1 + normal_operands(opcode), amnt;
+
+	switch (opcode) {
+	case SQ_OC_INT: {
+		enum sq_interrupt interrupt = code->bytecode[ip + 1].interrupt;
+		length += 1 + interrupt_operands(interrupt);
+
+		if (interrupt == SQ_INT_BOOK_NEW || interrupt == SQ_INT_CODEX_NEW || interrupt == SQ_INT_JOURNEY_CAPTURE) {
+			amnt = code->bytecode[ip + length].count;
+			length += 1 + amnt * (interrupt == SQ_INT_CODEX_NEW ? 2 : 1);
+		}
+
+		return length + 1;
+	}
+
+	case SQ_OC_COMEFROM:
+		return code->bytecode[ip + 2].index - ip;
+
+	case SQ_OC_TRYCATCH:
+		return length + 2;
+
+	case SQ_OC_ITER_NEXT:
+		return length + 3;
+
+	case SQ_OC_CALL:
+		return length + 1 + code->bytecode[ip + length].count + 1;
+
+	case SQ_OC_TAILCALL:
+		return length + 1 + code->bytecode[ip + length].count;
+
+	case SQ_OC_KWCALL:
+		amnt = code->bytecode[ip + length].count;
+		return length + 2 + amnt + 2 * code->bytecode[ip + length + 1].count + 1;
+
+	case SQ_OC_CLOAD:
+	case SQ_OC_GLOAD:
+	case SQ_OC_ILOAD:
+		return length + 2;
+
+	case SQ_OC_NOOP:
+	case SQ_OC_POPTRYCATCH:
+	case SQ_OC_RETURN:
+	case SQ_OC_THROW:
+	case SQ_OC_INDEX_ASSIGN:
+		return length;
+
+	default:
+		return length + 1;
+	}
+}
+
+// Returns whether the instruction at `ip` only loads a value into a local, without side effects.
+static bool is_pure_load(const struct sq_codeblock *code, unsigned ip, unsigned *dst) {
+	switch (code->bytecode[ip].opcode) {
+	case SQ_OC_CLOAD:
+		*dst = code->bytecode[ip + 2].index;
+		return true;
+
+	case SQ_OC_MOV:
+		*dst = code->bytecode[ip + 2].index;
+		return code->bytecode[ip + 1].index != *dst;
+
+	default:
+		return false;
+	}
+}
+
+// Returns whether the instruction at `ip` overwrites `local` without ever reading it.
+static bool overwrites_local(const struct sq_codeblock *code, unsigned ip, unsigned local) {
+	switch (code->bytecode[ip].opcode) {
+	case SQ_OC_CLOAD:
+	case SQ_OC_GLOAD:
+		return code->bytecode[ip + 2].index == local;
+
+	case SQ_OC_MOV:
+		return code->bytecode[ip + 1].index != local && code->bytecode[ip + 2].index == local;
+
+	default:
+		return false;
+	}
+}
+
+// Returns whether the instruction at `ip` does nothing at all, such as moving a local to itself.
+static bool is_redundant(const struct sq_codeblock *code, unsigned ip) {
+	switch (code->bytecode[ip].opcode) {
+	case SQ_OC_NOOP:
+		return true;
+
+	case SQ_OC_MOV:
+		if (code->bytecode[ip + 1].index == code->bytecode[ip + 2].index)
+			return true;
+		break;
+
+	case SQ_OC_JMP:
+		if (code->bytecode[ip + 1].index == ip + 2)
+			return true;
+		break;
+
+	default:
+		break;
+	}
+
+	return false;
+}
+
+static void remap_int(int *position, const unsigned *new_positions) {
+	if (*position != -1)
+		*position = new_positions[*position];
+}
+
+void sq_journey_pattern_optimize(struct sq_journey_pattern *pattern) {
+	struct sq_codeblock *code = &pattern->code;
+	unsigned *new_positions = xmalloc(sizeof_array(unsigned, code->codelen + 1));
+	unsigned ip, length, newlen = 0, dst;
+	int previous = -1; // the last instruction that was kept
+	bool *removed = xcalloc(code->codelen, sizeof(bool));
+
+	// First, find which instructions we can safely remove. Jumps to a removed
+	// instruction end up going to the next instruction that's kept.
+	for (ip = 0; ip < code->codelen; ip += length) {
+		length = instruction_length(code, ip);
+
+		if (is_redundant(code, ip)) {
+			removed[ip] = true;
+			continue;
+		}
+
+		// a load that's immediately overwritten never has its value read.
+		if (previous != -1 && is_pure_load(code, previous, &dst) && overwrites_local(code, ip, dst))
+			removed[previous] = true;
+
+		previous = ip;
+	}
+
+	for (ip = 0; ip < code->codelen; ip += length) {
+		length = instruction_length(code, ip);
+
+		for (unsigned i = 0; i < length; ++i)
+			new_positions[ip + i] = newlen + (removed[ip] ? 0 : i);
+
+		if (!removed[ip]) {
+			memmove(&code->bytecode[newlen], &code->bytecode[ip], sizeof_array(union sq_bytecode, length));
+			newlen += length;
+		}
+	}
+
+	new_positions[code->codelen] = newlen;
+	code->codelen = newlen;
+
+	// Then, update every jump target to point to where its instruction now is.
+	for (ip = 0; ip < code->codelen; ip += length) {
+		length = instruction_length(code, ip);
+		union sq_bytecode *bytecode = &code->bytecode[ip];
+
+		switch (bytecode[0].opcode) {
+		case SQ_OC_JMP:
+		case SQ_OC_TRYCATCH:
+			bytecode[1].index = new_positions[bytecode[1].index];
+			break;
+
+		case SQ_OC_JMP_FALSE:
+		case SQ_OC_JMP_TRUE:
+#ifndef SQ_NMOON_JOKE
+		case SQ_OC_WERE_JMP:
+#endif /* SQ_NMOON_JOKE */
+			bytecode[2].index = new_positions[bytecode[2].index];
+			break;
+
+		case SQ_OC_ITER_NEXT:
+			bytecode[4].index = new_positions[bytecode[4].index];
+			break;
+
+		case SQ_OC_COMEFROM:
+			for (unsigned i = 0; i < bytecode[1].count; ++i)
+				bytecode[3 + i].index = new_positions[bytecode[3 + i].index];
+
+			// the length of the comefrom is based on its end, so update it last.
+			bytecode[2].index = new_positions[bytecode[2].index];
+			length = bytecode[2].index - ip;
+			break;
+
+		default:
+			break;
+		}
+	}
+
+	pattern->start_index = new_positions[pattern->start_index];
+	remap_int(&pattern->condition_start, new_positions);
+	remap_int(&pattern->return_genus_start, new_positions);
+
+	for (unsigned i = 0; i < pattern->pargc; ++i) {
+		remap_int(&pattern->pargv[i].default_start, new_positions);
+		remap_int(&pattern->pargv[i].genus_start, new_positions);
+	}
+
+	for (unsigned i = 0; i < pattern->kwargc; ++i) {
+		remap_int(&pattern->kwargv[i].default_start, new_positions);
+		remap_int(&pattern->kwargv[i].genus_start, new_positions);
+	}
+
+	free(new_positions);
+	free(removed);
+}
+
+static bool is_iterable(sq_value value) {
+	return sq_value_is_book(value)
+		|| (sq_value_is_other(value) && sq_value_as_other(value)->kind == SQ_OK_RANGE);
+}
+
+// Fetches the element of `iterable` at `position` and advances it, returning
+// false once there are no more elements. Books are read as they currently are,
+// so pages added during iteration are visited too.
+static bool next_element(sq_value iterable, sq_value *position, sq_value *element) {
+	sq_numeral index = sq_value_as_numeral(*position);
+
+	if (sq_value_is_book(iterable)) {
+		struct sq_book *book = sq_value_as_book(iterable);
+
+		if (book->length <= (size_t) index)
+			return false;
+
+		*element = sq_value_clone(book->pages[index]);
+	} else {
+		struct sq_range *range = sq_other_as_range(sq_value_as_other(iterable));
+
+		if (sq_range_length(range) <= index)
+			return false;
+
+		*element = sq_value_new(range->start + index);
+	}
+
+	*position = sq_value_new(index + 1);
+	return true;
+}
+
 #define MAX_OPERAND_COUNT 3 // the max amount of operands (3) is from INDEX_ASSIGN
+static unsigned handler_operands[SQ_NUM_EXCEPTION_HANDLERS], handler_depths[SQ_NUM_EXCEPTION_HANDLERS];
+
 sq_value run_stackframe(struct sq_stackframe *sf) {
 	enum sq_opcode opcode;
 	sq_value operands[MAX_OPERAND_COUNT];
 	unsigned arity, index;
 	const struct sq_codeblock *code = &sf->pattern->code;
+	const unsigned exception_handlers_on_entry = current_exception_handler;
+	struct sq_program *program = sf->journey->program;
 
 	while (sf->ip < code->codelen) {
+		if (program->max_steps && program->max_steps < ++program->steps)
+			sq_throw("instruction limit of %lu exceeded", program->max_steps);
+
 		opcode = next_bytecode(sf).opcode;
 		arity = normal_operands(opcode);
 
@@ -699,13 +1774,18 @@ sq_value run_stackframe(struct sq_stackframe *sf) {
 			continue;
 
 		case SQ_OC_COMEFROM: {
-			int i;
-			int amnt = (int) next_index(sf);
+			unsigned i, amnt = next_count(sf), end = next_index(sf);
+
+			if (!amnt) {
+				sf->ip = end;
+				continue;
+			}
+
 			for (i = 0; i < amnt - 1; ++i)
 				if (!fork()) break;
-			sf->ip += i;
+
+			sf->ip = code->bytecode[sf->ip + i].index;
 			continue;
-			// todo: this should probably be fixed.
 		}
 
 		case SQ_OC_CALL: {
@@ -720,7 +1800,49 @@ sq_value run_stackframe(struct sq_stackframe *sf) {
 			continue;
 		}
 
+		case SQ_OC_TAILCALL: {
+			struct sq_args args = { .pargc = next_count(sf) };
+			args.pargv = xmalloc(sizeof_array(sq_value, args.pargc));
+
+			for (unsigned i = 0; i < args.pargc; ++i)
+				args.pargv[i] = sq_value_clone(*next_local(sf));
+
+			current_exception_handler = exception_handlers_on_entry;
+
+			// only journeys can reuse our frame; everything else is just called normally.
+			if (!sq_value_is_journey(operands[0])) {
+				sq_value result = sq_value_call(operands[0], args);
+				free(args.pargv);
+				return result;
+			}
+
+			// our locals are about to be freed, so make sure the journey outlives them.
+			tail_call.journey = sq_value_clone(operands[0]);
+			tail_call.args = args;
+			return SQ_NI;
+		}
+
+		case SQ_OC_KWCALL: {
+			unsigned pargc = next_count(sf), kwargc = next_count(sf);
+			sq_value pargv[pargc];
+			struct sq_arg_kw kwargv[kwargc];
+			struct sq_args args = { .pargc = pargc, .pargv = pargv, .kwargc = kwargc, .kwargv = kwargv };
+
+			for (unsigned i = 0; i < pargc; ++i)
+				args.pargv[i] = sq_value_clone(*next_local(sf));
+
+			for (unsigned i = 0; i < kwargc; ++i) {
+				args.kwargv[i].name = sq_value_as_text(code->consts[next_index(sf)])->ptr;
+				args.kwargv[i].value = sq_value_clone(*next_local(sf));
+			}
+
+			set_next_local(sf, sq_value_call(operands[0], args));
+			continue;
+		}
+
 		case SQ_OC_RETURN:
+			// `reward`ing from within an `attempt` discards its handlers.
+			current_exception_handler = exception_handlers_on_entry;
 			return sq_value_clone(operands[0]);
 
 		case SQ_OC_THROW:
@@ -731,15 +1853,43 @@ sq_value run_stackframe(struct sq_stackframe *sf) {
 			sq_exception_pop();
 			continue;
 
+		case SQ_OC_ITER_START:
+			if (!is_iterable(operands[0]))
+				sq_throw("cannot iterate over '%s'", sq_value_typename(operands[0]));
+
+			set_next_local(sf, sq_value_new((sq_numeral) 0));
+			continue;
+
+		case SQ_OC_ITER_NEXT: {
+			sq_value *position = next_local(sf), element;
+			unsigned dst = next_index(sf), end = next_index(sf);
+
+			if (!next_element(operands[0], position, &element)) {
+				sf->ip = end;
+				continue;
+			}
+
+			set_local(sf, dst, element);
+			continue;
+		}
+
 		case SQ_OC_TRYCATCH: {
 			// todo: maybe have this be within the `stackframe`?
-			unsigned catch_index = next_index(sf);
-			unsigned exception_index = next_index(sf);
+			// the operands are reread after a `catapult`, as any locals set
+			// after the `setjmp` (eg by nested `attempt`s) are clobbered.
+			handler_operands[current_exception_handler] = sf->ip;
+			handler_depths[current_exception_handler] = journey_depth;
+			sf->ip += 2;
 
 			if (!setjmp(exception_handlers[current_exception_handler++]))
 				continue;
 
-			sf->locals[exception_index] = exception;
+			sf->ip = handler_operands[current_exception_handler];
+			journey_depth = handler_depths[current_exception_handler]; // journeys we unwound never returned
+			unsigned catch_index = next_index(sf);
+			unsigned exception_index = next_index(sf);
+
+			set_local(sf, exception_index, exception);
 			exception = SQ_NI;
 			sf->ip = catch_index;
 			continue;
@@ -807,6 +1957,26 @@ sq_value run_stackframe(struct sq_stackframe *sf) {
 			set_next_local(sf, sq_value_pow(operands[0], operands[1]));
 			continue;
 
+		case SQ_OC_BAND:
+			set_next_local(sf, sq_value_band(operands[0], operands[1]));
+			continue;
+
+		case SQ_OC_BOR:
+			set_next_local(sf, sq_value_bor(operands[0], operands[1]));
+			continue;
+
+		case SQ_OC_BXOR:
+			set_next_local(sf, sq_value_bxor(operands[0], operands[1]));
+			continue;
+
+		case SQ_OC_SHL:
+			set_next_local(sf, sq_value_shl(operands[0], operands[1]));
+			continue;
+
+		case SQ_OC_SHR:
+			set_next_local(sf, sq_value_shr(operands[0], operands[1]));
+			continue;
+
 		case SQ_OC_INDEX:
 			set_next_local(sf, sq_value_index(operands[0], operands[1]));
 			continue;
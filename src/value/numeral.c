@@ -1,9 +1,12 @@
 #include <squire/numeral.h>
 #include <squire/shared.h>
 #include <squire/text.h>
+#include <squire/value.h>
 
 #include <ctype.h>
 #include <string.h>
+#include <stdlib.h>
+#include <stdio.h>
 
 enum roman_numeral {
 	SQ_TK_ROMAN_I = 1,
@@ -43,15 +46,37 @@ struct sq_text sq_text_zero = SQ_TEXT_STATIC("0");
 struct sq_text sq_text_zero = SQ_TEXT_STATIC("N"); 
 #endif /* SQ_NUMERAL_TO_ARABIC */
 
+// Decimals are always written in arabic, using as few digits as are needed to
+// read the same decimal back in. Whole decimals end with `.0`, so that they
+// can be told apart from numerals.
+struct sq_text *sq_decimal_to_text(sq_decimal decimal) {
+	char buf[32];
+
+	for (int precision = 1; precision <= 17; ++precision) {
+		snprintf(buf, sizeof(buf) - 2, "%.*g", precision, decimal);
+
+		if (sq_value_new_decimal(strtod(buf, NULL)) == sq_value_new_decimal(decimal))
+			break;
+	}
+
+	if (!strpbrk(buf, ".en"))
+		strcat(buf, ".0");
+
+	return sq_text_new(strdup(buf));
+}
+
 // lol this is so bad.
 struct sq_text *sq_numeral_to_roman(sq_numeral numeral) {
 	if (!numeral)
 		return &sq_text_zero;
 
-	struct sq_text *buf = sq_text_allocate(40); // todo: find an actual max size lol
+	if (numeral < -SQ_NUMERAL_ROMAN_MAX || SQ_NUMERAL_ROMAN_MAX < numeral)
+		return sq_numeral_to_arabic(numeral);
+
+	// a sign, one `M` per thousand, and at most `DCCCLXXXVIII` for the rest.
+	struct sq_text *buf = sq_text_allocate(1 + SQ_NUMERAL_ROMAN_MAX / 1000 + 12);
 	char *ret = buf->ptr;
 
-	// todo: if numeral is min possible.
 	if (numeral < 0) {
 		*ret++ = '-';
 		numeral *= -1;
@@ -154,7 +179,11 @@ sq_numeral sq_roman_to_numeral(const char *input, const char **output) {
 		case 'C': parsed = SQ_TK_ROMAN_C; break;
 		case 'D': parsed = SQ_TK_ROMAN_D; break;
 		case 'M': parsed = SQ_TK_ROMAN_M; break;
-		case '_': continue; // ignore `_` in roman numeral literals
+		case '_':
+			// `_`s can separate digits, eg `M_CM`; otherwise we're an identifier, eg `I_am`.
+			if (!input[1] || !strchr("IVXLCDM", input[1])) return -1;
+			++input;
+			continue;
 		default:
 			// followed by any other alphanumerics, we aren't a roman numeral.
 			if (isalnum(*input)) return -1;
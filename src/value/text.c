@@ -1,9 +1,13 @@
 #include <squire/text.h>
 #include <squire/shared.h>
+#include <squire/book.h>
+#include <squire/numeral.h>
 
 #include <assert.h>
 #include <stdlib.h>
 #include <string.h>
+#include <wctype.h>
+#include <locale.h>
 
 struct sq_text sq_text_empty = SQ_TEXT_STATIC("");
 
@@ -49,3 +53,327 @@ void sq_text_dealloc(struct sq_text *text) {
 	free(text->ptr);
 	free(text);
 }
+
+// utf-8 continuation bytes are always of the form `10xxxxxx`.
+#define IS_CONTINUATION_BYTE(c) (((c) & 0xC0) == 0x80)
+
+struct sq_text *sq_text_char_at(const struct sq_text *text, sq_numeral index) {
+	unsigned start, end;
+
+	assert(index != 0);
+
+	if (0 < index) {
+		// walk forwards, stopping at the `index`th character start.
+		for (start = 0; start < text->length; ++start)
+			if (!IS_CONTINUATION_BYTE(text->ptr[start]) && !--index)
+				break;
+
+		if (start == text->length)
+			return NULL;
+	} else {
+		// walk backwards, so we don't have to traverse the entire text.
+		for (start = text->length; start--;)
+			if (!IS_CONTINUATION_BYTE(text->ptr[start]) && !++index)
+				break;
+
+		if (start == (unsigned) -1)
+			return NULL;
+	}
+
+	for (end = start + 1; end < text->length && IS_CONTINUATION_BYTE(text->ptr[end]); ++end);
+
+	return sq_text_new2(strndup(text->ptr + start, end - start), end - start);
+}
+
+// gets the byte offset of the `index`th (zero-based) character, or `text->length` if there's none.
+static unsigned char_offset(const struct sq_text *text, unsigned index) {
+	unsigned offset;
+
+	for (offset = 0; offset < text->length; ++offset)
+		if (!IS_CONTINUATION_BYTE(text->ptr[offset]) && !index--)
+			break;
+
+	return offset;
+}
+
+struct sq_text *sq_text_substr(const struct sq_text *text, sq_numeral start, sq_numeral count) {
+	sq_numeral nchars = sq_text_nchars(text);
+
+	assert(start != 0);
+
+	// convert `start` to a zero-based index.
+	if (start < 0)
+		start += nchars;
+	else
+		--start;
+
+	if (start < 0 || nchars <= start || count <= 0)
+		return &sq_text_empty;
+
+	if (nchars - start < count)
+		count = nchars - start;
+
+	unsigned begin = char_offset(text, start), end = char_offset(text, start + count);
+
+	return sq_text_new2(strndup(text->ptr + begin, end - begin), end - begin);
+}
+
+// the `C` locale only knows about ascii, so a utf-8 one is used for case conversions.
+static locale_t utf8_locale(void) {
+	static locale_t locale;
+
+	if (!locale && !(locale = newlocale(LC_CTYPE_MASK, "C.UTF-8", (locale_t) 0)))
+		locale = newlocale(LC_CTYPE_MASK, "C", (locale_t) 0);
+
+	return locale;
+}
+
+// decodes the utf-8 character at `ptr`, storing its length in `length`. Invalid
+// sequences are decoded as a single byte.
+static wint_t decode_utf8(const unsigned char *ptr, unsigned remaining, unsigned *length) {
+	unsigned needed = (ptr[0] < 0x80) ? 0 : (ptr[0] & 0xE0) == 0xC0 ? 1
+		: (ptr[0] & 0xF0) == 0xE0 ? 2 : (ptr[0] & 0xF8) == 0xF0 ? 3 : 4;
+
+	if (needed == 4 || remaining <= needed)
+		goto invalid;
+
+	wint_t codepoint = needed ? ptr[0] & (0x3F >> needed) : ptr[0];
+
+	for (unsigned i = 1; i <= needed; ++i) {
+		if (!IS_CONTINUATION_BYTE(ptr[i]))
+			goto invalid;
+
+		codepoint = (codepoint << 6) | (ptr[i] & 0x3F);
+	}
+
+	*length = needed + 1;
+	return codepoint;
+
+invalid:
+	*length = 1;
+	return WEOF;
+}
+
+static unsigned encode_utf8(wint_t codepoint, char *dst) {
+	if (codepoint < 0x80) {
+		dst[0] = codepoint;
+		return 1;
+	}
+
+	if (codepoint < 0x800) {
+		dst[0] = 0xC0 | (codepoint >> 6);
+		dst[1] = 0x80 | (codepoint & 0x3F);
+		return 2;
+	}
+
+	if (codepoint < 0x10000) {
+		dst[0] = 0xE0 | (codepoint >> 12);
+		dst[1] = 0x80 | ((codepoint >> 6) & 0x3F);
+		dst[2] = 0x80 | (codepoint & 0x3F);
+		return 3;
+	}
+
+	dst[0] = 0xF0 | (codepoint >> 18);
+	dst[1] = 0x80 | ((codepoint >> 12) & 0x3F);
+	dst[2] = 0x80 | ((codepoint >> 6) & 0x3F);
+	dst[3] = 0x80 | (codepoint & 0x3F);
+	return 4;
+}
+
+static struct sq_text *change_case(const struct sq_text *text, wint_t (*convert)(wint_t, locale_t)) {
+	// a character's case counterpart is never more than half again as long.
+	char *result = xmalloc(text->length * 2 + 1);
+	unsigned length = 0, charlen;
+	wint_t codepoint;
+
+	for (unsigned i = 0; i < text->length; i += charlen) {
+		codepoint = decode_utf8((const unsigned char *) text->ptr + i, text->length - i, &charlen);
+
+		if (codepoint == WEOF)
+			result[length++] = text->ptr[i];
+		else
+			length += encode_utf8(convert(codepoint, utf8_locale()), result + length);
+	}
+
+	result[length] = '\0';
+	return sq_text_new2(xrealloc(result, length + 1), length);
+}
+
+struct sq_text *sq_text_upper(const struct sq_text *text) {
+	return change_case(text, towupper_l);
+}
+
+struct sq_text *sq_text_lower(const struct sq_text *text) {
+	return change_case(text, towlower_l);
+}
+
+struct sq_text *sq_text_trim(const struct sq_text *text, bool start, bool end) {
+	unsigned begin = 0, finish = text->length, charlen;
+	bool is_leading = true;
+	wint_t codepoint;
+
+	// `finish` is just past the last non-whitespace character we've seen so far.
+	for (unsigned i = 0; i < text->length; i += charlen) {
+		codepoint = decode_utf8((const unsigned char *) text->ptr + i, text->length - i, &charlen);
+
+		if (codepoint != WEOF && iswspace_l(codepoint, utf8_locale())) {
+			if (is_leading && start)
+				begin = i + charlen;
+			continue;
+		}
+
+		is_leading = false;
+		if (end)
+			finish = i + charlen;
+	}
+
+	if (is_leading && end)
+		finish = begin;
+
+	if (finish <= begin)
+		return &sq_text_empty;
+
+	return sq_text_new2(strndup(text->ptr + begin, finish - begin), finish - begin);
+}
+
+struct sq_book *sq_text_split(const struct sq_text *text, const struct sq_text *sep) {
+	assert(sep->length != 0);
+
+	struct sq_book *book = sq_book_allocate(4);
+	const char *start = text->ptr, *found;
+
+	while ((found = strstr(start, sep->ptr))) {
+		sq_book_insert(book, book->length, sq_value_new(sq_text_new2(strndup(start, found - start), found - start)));
+		start = found + sep->length;
+	}
+
+	unsigned rest = text->ptr + text->length - start;
+	sq_book_insert(book, book->length, sq_value_new(sq_text_new2(strndup(start, rest), rest)));
+
+	return book;
+}
+
+struct sq_text *sq_text_replace(const struct sq_text *text, const struct sq_text *from, const struct sq_text *to) {
+	assert(from->length != 0);
+
+	size_t length = 0, cap = (size_t) text->length + 1;
+	char *result = xmalloc(cap);
+	const char *start = text->ptr, *found;
+
+	while ((found = strstr(start, from->ptr))) {
+		size_t before = found - start;
+
+		// free `result` first, as checking the length catapults.
+		if (sq_value_max_length < length + before + to->length)
+			free(result);
+		sq_value_check_length(length + before + to->length);
+
+		if (cap <= length + before + to->length)
+			result = xrealloc(result, cap = (length + before + to->length) * 2 + 1);
+
+		memcpy(result + length, start, before);
+		memcpy(result + length + before, to->ptr, to->length);
+		length += before + to->length;
+		start = found + from->length;
+	}
+
+	size_t rest = text->ptr + text->length - start;
+	if (sq_value_max_length < length + rest)
+		free(result);
+	sq_value_check_length(length + rest);
+
+	if (cap <= length + rest)
+		result = xrealloc(result, cap = length + rest + 1);
+
+	memcpy(result + length, start, rest);
+	length += rest;
+	result[length] = '\0';
+
+	return sq_text_new2(xrealloc(result, length + 1), length);
+}
+
+bool sq_text_starts_with(const struct sq_text *text, const struct sq_text *affix) {
+	return affix->length <= text->length && !memcmp(text->ptr, affix->ptr, affix->length);
+}
+
+bool sq_text_ends_with(const struct sq_text *text, const struct sq_text *affix) {
+	return affix->length <= text->length
+		&& !memcmp(text->ptr + text->length - affix->length, affix->ptr, affix->length);
+}
+
+unsigned sq_text_nchars(const struct sq_text *text) {
+	unsigned nchars = 0;
+
+	for (unsigned i = 0; i < text->length; ++i)
+		if (!IS_CONTINUATION_BYTE(text->ptr[i]))
+			++nchars;
+
+	return nchars;
+}
+
+struct sq_text *sq_text_format(const struct sq_text *format, sq_value args) {
+	static struct sq_text percent = SQ_TEXT_STATIC("%");
+	sq_value *argv = &args;
+	unsigned argc = 1, argn = 0;
+
+	if (sq_value_is_book(args)) {
+		argv = sq_value_as_book(args)->pages;
+		argc = sq_value_as_book(args)->length;
+	}
+
+	unsigned len = 0, cap = format->length + 1;
+	char *str = xmalloc(cap);
+
+	for (unsigned i = 0; i < format->length; ++i) {
+		if (format->ptr[i] != '%') {
+			if (cap <= len + 1)
+				str = xrealloc(str, cap *= 2);
+			str[len++] = format->ptr[i];
+			continue;
+		}
+
+		struct sq_text *inner;
+
+		switch (++i < format->length ? format->ptr[i] : '\0') {
+		case '%':
+			inner = &percent;
+			break;
+
+		case 's':
+		case 'd':
+			if (argc <= argn) {
+				free(str);
+				sq_throw("not enough arguments for format (only %u given)", argc);
+			}
+
+			if (format->ptr[i] == 's')
+				inner = sq_value_to_text(argv[argn++]);
+			else
+				inner = sq_numeral_to_arabic(sq_value_to_numeral(argv[argn++]));
+			break;
+
+		case '\0':
+			free(str);
+			sq_throw("incomplete format specifier at end of text");
+
+		default:
+			free(str);
+			sq_throw("unknown format specifier '%%%c'", format->ptr[i]);
+		}
+
+		if (cap <= inner->length + len)
+			str = xrealloc(str, cap = inner->length + len * 2 + 1);
+
+		memcpy(str + len, inner->ptr, inner->length);
+		len += inner->length;
+		sq_text_free(inner);
+	}
+
+	if (argn != argc) {
+		free(str);
+		sq_throw("too many arguments for format (%u given, %u used)", argc, argn);
+	}
+
+	str[len] = '\0';
+	return sq_text_new2(str, len);
+}
@@ -8,12 +8,15 @@
 #include <squire/other/other.h>
 
 #include <string.h>
+#include <ctype.h>
+#include <errno.h>
 #include <inttypes.h>
 #include <limits.h>
 #include <math.h>
 
 #define AS_TEXT sq_value_as_text
 #define AS_NUMBER sq_value_as_numeral
+#define AS_DECIMAL sq_value_as_decimal
 #define AS_FORM sq_value_as_form
 #define AS_IMITATION sq_value_as_imitation
 #define AS_JOURNEY sq_value_as_function
@@ -23,10 +26,38 @@
 #define TYPENAME sq_value_typename
 #define AS_STR(c) (AS_TEXT(c)->ptr)
 
+size_t sq_value_max_length = 1 << 26;
+
+void sq_value_check_length(size_t length) {
+	if (sq_value_max_length < length)
+		sq_throw("allocation limit of %zu exceeded", sq_value_max_length);
+}
+
 void sq_value_dump(sq_value value) {
 	sq_value_dump_to(stdout, value);
 }
 
+static void dump_text(FILE *out, const char *text) {
+	fputs("Text(\"", out);
+
+	for (; *text; ++text) {
+		switch (*text) {
+		case '"':  fputs("\\\"", out); break;
+		case '\\': fputs("\\\\", out); break;
+		case '\n': fputs("\\n", out); break;
+		case '\t': fputs("\\t", out); break;
+		case '\r': fputs("\\r", out); break;
+		default:
+			if ((unsigned char) *text < ' ' || *text == 0x7f)
+				fprintf(out, "\\x%02x", (unsigned char) *text);
+			else
+				putc(*text, out);
+		}
+	}
+
+	fputs("\")", out);
+}
+
 void sq_value_dump_to(FILE *out, sq_value value) {
 	switch (SQ_VTAG(value)) {
 	case SQ_G_OTHER:
@@ -43,8 +74,15 @@ void sq_value_dump_to(FILE *out, sq_value value) {
 		fprintf(out, "Numeral(%"PRId64")", AS_NUMBER(value));
 		break;
 
+	case SQ_G_DECIMAL: {
+		struct sq_text *text = sq_decimal_to_text(AS_DECIMAL(value));
+		fprintf(out, "Decimal(%s)", text->ptr);
+		sq_text_free(text);
+		break;
+	}
+
 	case SQ_G_TEXT:
-		fprintf(out, "Text(%s)", AS_STR(value));
+		dump_text(out, AS_STR(value));
 		break;
 
 	case SQ_G_FORM:
@@ -146,6 +184,7 @@ const char *sq_value_typename(sq_value value) {
 			return value == SQ_NI ? "Ni" : "Veracity";
 
 	case SQ_G_NUMERAL: return "Numeral";
+	case SQ_G_DECIMAL: return "Decimal";
 	case SQ_G_TEXT: return "Text";
 	case SQ_G_IMITATION: return "Imitation";
 	case SQ_G_JOURNEY: return "Journey";
@@ -160,6 +199,7 @@ sq_value sq_value_genus(sq_value value) {
 	static struct sq_text KIND_VERACITY = SQ_TEXT_STATIC("Veracity");
 	static struct sq_text KIND_NI = SQ_TEXT_STATIC("Ni");
 	static struct sq_text KIND_NUMERAL = SQ_TEXT_STATIC("Numeral");
+	static struct sq_text KIND_DECIMAL = SQ_TEXT_STATIC("Decimal");
 	static struct sq_text KIND_TEXT = SQ_TEXT_STATIC("Text");
 	static struct sq_text KIND_FUNCTION = SQ_TEXT_STATIC("Journey");
 	static struct sq_text KIND_FORM = SQ_TEXT_STATIC("Form");
@@ -176,6 +216,9 @@ sq_value sq_value_genus(sq_value value) {
 	case SQ_G_NUMERAL:
 		return sq_value_new(&KIND_NUMERAL);
 
+	case SQ_G_DECIMAL:
+		return sq_value_new(&KIND_DECIMAL);
+
 	case SQ_G_TEXT:
 		return sq_value_new(&KIND_TEXT);
 
@@ -205,6 +248,18 @@ bool sq_value_not(sq_value arg) {
 
 bool sq_value_eql(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
+	case SQ_G_NUMERAL:
+		if (sq_value_is_decimal(rhs))
+			return (sq_decimal) AS_NUMBER(lhs) == AS_DECIMAL(rhs);
+
+		return lhs == rhs;
+
+	case SQ_G_DECIMAL:
+		if (!sq_value_is_decimal(rhs) && !sq_value_is_numeral(rhs))
+			return false;
+
+		return AS_DECIMAL(lhs) == sq_value_to_decimal(rhs);
+
 	case SQ_G_TEXT:
 		return sq_value_is_text(rhs) && !strcmp(AS_STR(lhs), AS_STR(rhs));
 
@@ -240,8 +295,15 @@ bool sq_value_eql(sq_value lhs, sq_value rhs) {
 		struct sq_journey *eql = sq_imitation_lookup_change(AS_IMITATION(lhs), "==");
 		sq_value args[2] = { lhs, rhs };
 
-		if (eql != NULL)
-			return sq_journey_run_deprecated(eql, 2, args);
+		if (eql != NULL) {
+			sq_value result = sq_journey_run_deprecated(eql, 2, args);
+			bool veracity = sq_value_to_veracity(result);
+
+			sq_value_free(result);
+			return veracity;
+		}
+
+		// otherwise, imitations are only equal to themselves.
 		// fallthrough
 	}
 
@@ -253,18 +315,29 @@ bool sq_value_eql(sq_value lhs, sq_value rhs) {
 sq_numeral sq_value_cmp(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
 	case SQ_G_NUMERAL: {
+		if (sq_value_is_decimal(rhs))
+			return -sq_value_cmp(rhs, lhs);
+
 		sq_numeral l = AS_NUMBER(lhs);
 		sq_numeral r = sq_value_to_numeral(rhs);
 
 		return l < r ? -1 : l == r ? 0 : 1;
 	}
 
-	case SQ_G_TEXT:
-		// todo: free text
-		return strcmp(AS_STR(lhs), sq_value_to_text(rhs)->ptr);
+	case SQ_G_DECIMAL: {
+		sq_decimal l = AS_DECIMAL(lhs);
+		sq_decimal r = sq_value_to_decimal(rhs);
 
-	case SQ_G_IMITATION:
-		todo("cmp imitation");
+		return l < r ? -1 : l == r ? 0 : 1;
+	}
+
+	case SQ_G_TEXT: {
+		struct sq_text *r = sq_value_to_text(rhs);
+		int cmp = strcmp(AS_STR(lhs), r->ptr);
+
+		sq_text_free(r);
+		return cmp < 0 ? -1 : cmp == 0 ? 0 : 1;
+	}
 
 	default:
 		die("cannot compare '%s' with '%s'", TYPENAME(lhs), TYPENAME(rhs));
@@ -275,10 +348,25 @@ sq_numeral sq_value_cmp(sq_value lhs, sq_value rhs) {
 	}
 }
 
+// Numerals only have `64 - SQ_VSHIFT` bits, so results outside of that range
+// are reported as errors instead of silently wrapping around.
+#define NUMERAL_MAX (INT64_MAX >> SQ_VSHIFT)
+#define NUMERAL_MIN (INT64_MIN >> SQ_VSHIFT)
+
+static sq_value new_checked_numeral(bool overflowed, sq_numeral result, const char *operation) {
+	if (overflowed || result < NUMERAL_MIN || NUMERAL_MAX < result)
+		die("numeral overflow when %s", operation);
+
+	return sq_value_new(result);
+}
+
 sq_value sq_value_neg(sq_value arg) {
 	switch (SQ_VTAG(arg)) {
 	case SQ_G_NUMERAL:
-		return sq_value_new(-AS_NUMBER(arg));
+		return new_checked_numeral(false, -AS_NUMBER(arg), "negating");
+
+	case SQ_G_DECIMAL:
+		return sq_value_new(-AS_DECIMAL(arg));
 
 	case SQ_G_IMITATION: {
 		struct sq_journey *neg = sq_imitation_lookup_change(AS_IMITATION(arg), "-@");
@@ -296,19 +384,12 @@ sq_value sq_value_neg(sq_value arg) {
 sq_value sq_value_index(sq_value value, sq_value key) {
 	switch (SQ_VTAG(value)) {
 	case SQ_G_TEXT: {
-		int index = sq_value_to_numeral(key);
-
-		if (!index--) sq_throw("cannot index by N.");
-		if (index < 0)
-			index += AS_TEXT(value)->length + 1;
+		sq_numeral index = sq_value_to_numeral(key);
 
-		if (index < 0 || AS_TEXT(value)->length <= (unsigned) index)
-			return SQ_NI;
+		if (!index) sq_throw("cannot index by N.");
 
-		char *c = xmalloc(sizeof_array(char, 2));
-		c[0] = AS_STR(value)[index];
-		c[1] = '\0';
-		return sq_value_new(sq_text_new2(c, 1));
+		struct sq_text *chr = sq_text_char_at(AS_TEXT(value), index);
+		return chr == NULL ? SQ_NI : sq_value_new(chr);
 	}
 
 	case SQ_G_BOOK:
@@ -368,15 +449,33 @@ sq_value sq_value_add(sq_value lhs, sq_value rhs) {
 	}
 
 	switch (SQ_VTAG(lhs)) {
-	case SQ_G_NUMERAL:
-		return sq_value_new(AS_NUMBER(lhs) + sq_value_to_numeral(rhs));
+	case SQ_G_NUMERAL: {
+		if (sq_value_is_decimal(rhs))
+			return sq_value_new((sq_decimal) AS_NUMBER(lhs) + AS_DECIMAL(rhs));
+
+		sq_numeral result;
+		bool overflowed = __builtin_add_overflow(AS_NUMBER(lhs), sq_value_to_numeral(rhs), &result);
+		return new_checked_numeral(overflowed, result, "adding");
+	}
+
+	case SQ_G_DECIMAL:
+		return sq_value_new(AS_DECIMAL(lhs) + sq_value_to_decimal(rhs));
 
 	case SQ_G_TEXT: {
-		struct sq_text *rstr = sq_value_to_text(rhs);
-		struct sq_text *result = sq_text_allocate(AS_TEXT(lhs)->length + rstr->length);
+		struct sq_text *lstr = AS_TEXT(lhs), *rstr = sq_value_to_text(rhs);
 
-		memcpy(result->ptr, AS_STR(lhs), AS_TEXT(lhs)->length);
-		memcpy(result->ptr + AS_TEXT(lhs)->length, rstr->ptr, AS_TEXT(rhs)->length + 1);
+		// if either side's empty, we can just reuse the other one.
+		if (!rstr->length)
+			return sq_value_new(sq_text_clone(lstr));
+
+		if (!lstr->length)
+			return sq_value_new(rstr);
+
+		sq_value_check_length((size_t) lstr->length + rstr->length);
+		struct sq_text *result = sq_text_allocate(lstr->length + rstr->length);
+
+		memcpy(result->ptr, lstr->ptr, lstr->length);
+		memcpy(result->ptr + lstr->length, rstr->ptr, rstr->length + 1);
 
 		// sq_text_free(rstr);
 		// if (free_lhs) sq_value_free(lhs);
@@ -387,8 +486,23 @@ sq_value sq_value_add(sq_value lhs, sq_value rhs) {
 		if (sq_value_is_journey(rhs))
 			return sq_value_new(sq_book_select(AS_BOOK(lhs), AS_JOURNEY(rhs)));
 
-		struct sq_book *lary = AS_BOOK(lhs), *rary = sq_value_to_book(rhs);
+		struct sq_book *lary = AS_BOOK(lhs);
+
+		// anything other than a book is added as a single page.
+		if (!sq_value_is_book(rhs)) {
+			sq_value_check_length((size_t) lary->length + 1);
+			sq_value *pages = xmalloc(sizeof_array(sq_value, lary->length + 1));
+
+			for (unsigned i = 0; i < lary->length; ++i)
+				pages[i] = sq_value_clone(lary->pages[i]);
+			pages[lary->length] = sq_value_clone(rhs);
 
+			return sq_value_new(sq_book_new2(lary->length + 1, pages));
+		}
+
+		struct sq_book *rary = AS_BOOK(rhs);
+
+		sq_value_check_length((size_t) lary->length + rary->length);
 		unsigned length = lary->length + rary->length;
 		sq_value *pages = xmalloc(sizeof_array(sq_value, length));
 
@@ -398,7 +512,6 @@ sq_value sq_value_add(sq_value lhs, sq_value rhs) {
 		for (unsigned i = 0; i < rary->length; ++i)
 			pages[lary->length + i] = sq_value_clone(rary->pages[i]);
 
-		// sq_book_free(rary);
 		return sq_value_new(sq_book_new2(length, pages));
 	}
 
@@ -436,8 +549,17 @@ sq_value sq_value_add(sq_value lhs, sq_value rhs) {
 
 sq_value sq_value_sub(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
-	case SQ_G_NUMERAL:
-		return sq_value_new(AS_NUMBER(lhs) - sq_value_to_numeral(rhs));
+	case SQ_G_NUMERAL: {
+		if (sq_value_is_decimal(rhs))
+			return sq_value_new((sq_decimal) AS_NUMBER(lhs) - AS_DECIMAL(rhs));
+
+		sq_numeral result;
+		bool overflowed = __builtin_sub_overflow(AS_NUMBER(lhs), sq_value_to_numeral(rhs), &result);
+		return new_checked_numeral(overflowed, result, "subtracting");
+	}
+
+	case SQ_G_DECIMAL:
+		return sq_value_new(AS_DECIMAL(lhs) - sq_value_to_decimal(rhs));
 
 	case SQ_G_BOOK:
 		todo("set difference");
@@ -462,15 +584,27 @@ sq_value sq_value_sub(sq_value lhs, sq_value rhs) {
 
 sq_value sq_value_mul(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
-	case SQ_G_NUMERAL:
-		return sq_value_new(AS_NUMBER(lhs) * sq_value_to_numeral(rhs));
+	case SQ_G_NUMERAL: {
+		if (sq_value_is_decimal(rhs))
+			return sq_value_new((sq_decimal) AS_NUMBER(lhs) * AS_DECIMAL(rhs));
+
+		sq_numeral result;
+		bool overflowed = __builtin_mul_overflow(AS_NUMBER(lhs), sq_value_to_numeral(rhs), &result);
+		return new_checked_numeral(overflowed, result, "multiplying");
+	}
+
+	case SQ_G_DECIMAL:
+		return sq_value_new(AS_DECIMAL(lhs) * sq_value_to_decimal(rhs));
 
 	case SQ_G_TEXT: {
 		sq_numeral amnt = sq_value_to_numeral(rhs);
+		if (amnt < 0)
+			sq_throw("cannot repeat text a negative amount of times (%"PRId64")", amnt);
 		if (amnt == 0 || AS_TEXT(lhs)->length == 0)
 			return sq_value_new(&sq_text_empty);
-		if (amnt < 0 || amnt >= UINT_MAX || (amnt * AS_TEXT(lhs)->length) >= UINT_MAX)
+		if (amnt >= UINT_MAX || (amnt * AS_TEXT(lhs)->length) >= UINT_MAX)
 			sq_throw("text multiplication by %"PRId64" is out of range", amnt);
+		sq_value_check_length(amnt * AS_TEXT(lhs)->length);
 		if (amnt == 1)
 			return sq_value_new(sq_text_clone(AS_TEXT(lhs)));
 
@@ -491,14 +625,20 @@ sq_value sq_value_mul(sq_value lhs, sq_value rhs) {
 		if (sq_value_is_numeral(rhs)) {
 			sq_numeral num = AS_NUMBER(rhs);
 			if (num < 0) sq_throw("cannot repeat by %"PRId64" is out of range", num);
+			size_t length;
+			if (__builtin_mul_overflow((size_t) num, book->length, &length))
+				length = SIZE_MAX;
+			sq_value_check_length(length);
 			return sq_value_new(sq_book_repeat(book, num));
 		}
 
 		if (sq_value_is_text(rhs))
 			return sq_value_new(sq_book_join(book, AS_TEXT(rhs)));
 
-		if (sq_value_is_book(rhs))
+		if (sq_value_is_book(rhs)) {
+			sq_value_check_length((size_t) book->length * AS_BOOK(rhs)->length);
 			return sq_value_new(sq_book_product(book, AS_BOOK(rhs)));
+		}
 
 		if (sq_value_is_journey(rhs))
 			return sq_value_new(sq_book_map(book, AS_JOURNEY(rhs)));
@@ -523,11 +663,18 @@ sq_value sq_value_mul(sq_value lhs, sq_value rhs) {
 sq_value sq_value_div(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
 	case SQ_G_NUMERAL: {
+		if (sq_value_is_decimal(rhs))
+			return sq_value_new((sq_decimal) AS_NUMBER(lhs) / AS_DECIMAL(rhs));
+
 		sq_numeral rnum = sq_value_to_numeral(rhs);
 		if (!rnum) die("cannot divide by N");
 		return sq_value_new(AS_NUMBER(lhs) / rnum);
 	}
 
+	// unlike numerals, decimals follow IEEE-754, and so dividing by zero gives an infinity.
+	case SQ_G_DECIMAL:
+		return sq_value_new(AS_DECIMAL(lhs) / sq_value_to_decimal(rhs));
+
 	case SQ_G_IMITATION: {
 		struct sq_journey *div = sq_imitation_lookup_change(AS_IMITATION(lhs), "/");
 		sq_value args[2] = { lhs, rhs };
@@ -547,11 +694,36 @@ sq_value sq_value_div(sq_value lhs, sq_value rhs) {
 sq_value sq_value_mod(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
 	case SQ_G_NUMERAL: {
+		if (sq_value_is_decimal(rhs))
+			goto decimal_modulo;
+
 		sq_numeral rnum = sq_value_to_numeral(rhs);
 		if (!rnum) die("cannot modulo by N");
-		return sq_value_new(AS_NUMBER(lhs) % rnum);
+
+		// Modulo is floored, so the result always has the same sign as the
+		// divisor (eg `-VII % III` is `II`, and `VII % -III` is `-II`).
+		if (rnum == -1) return sq_value_new((sq_numeral) 0);
+		sq_numeral result = AS_NUMBER(lhs) % rnum;
+		if (result && (result < 0) != (rnum < 0))
+			result += rnum;
+
+		return sq_value_new(result);
 	}
 
+	case SQ_G_DECIMAL:
+	decimal_modulo: {
+		sq_decimal rdec = sq_value_to_decimal(rhs);
+		sq_decimal result = fmod(sq_value_to_decimal(lhs), rdec);
+
+		if (result && (result < 0) != (rdec < 0))
+			result += rdec;
+
+		return sq_value_new(result);
+	}
+
+	case SQ_G_TEXT:
+		return sq_value_new(sq_text_format(AS_TEXT(lhs), rhs));
+
 	case SQ_G_BOOK:;
 		struct sq_book *book = AS_BOOK(lhs);
 
@@ -580,11 +752,40 @@ sq_value sq_value_mod(sq_value lhs, sq_value rhs) {
 sq_value sq_value_pow(sq_value lhs, sq_value rhs) {
 	switch (SQ_VTAG(lhs)) {
 	case SQ_G_NUMERAL: {
-		sq_numeral rnum = sq_value_to_numeral(rhs);
-		if (!rnum) die("cannot modulo by N");
-		return sq_value_new((sq_numeral) pow(AS_NUMBER(lhs), rnum));
+		if (sq_value_is_decimal(rhs))
+			return sq_value_new(pow(AS_NUMBER(lhs), AS_DECIMAL(rhs)));
+
+		sq_numeral base = AS_NUMBER(lhs), exponent = sq_value_to_numeral(rhs), result = 1;
+
+		if (exponent < 0) {
+			if (!base) die("cannot raise N to a negative power");
+
+			// only `I` and `-I` have integral reciprocals.
+			if (base != 1 && base != -1)
+				return sq_value_new((sq_numeral) 0);
+
+			return sq_value_new((sq_numeral) (base == 1 || !(exponent & 1) ? 1 : -1));
+		}
+
+		bool overflowed = false;
+
+		// only square `base` when it's needed, so its last squaring can't overflow.
+		while (true) {
+			if (exponent & 1)
+				overflowed |= __builtin_mul_overflow(result, base, &result);
+
+			if (!(exponent >>= 1))
+				break;
+
+			overflowed |= __builtin_mul_overflow(base, base, &base);
+		}
+
+		return new_checked_numeral(overflowed, result, "exponentiating");
 	}
 
+	case SQ_G_DECIMAL:
+		return sq_value_new(pow(AS_DECIMAL(lhs), sq_value_to_decimal(rhs)));
+
 	case SQ_G_IMITATION: {
 		struct sq_journey *pow = sq_imitation_lookup_change(AS_IMITATION(lhs), "^");
 		sq_value args[2] = { lhs, rhs };
@@ -600,6 +801,65 @@ sq_value sq_value_pow(sq_value lhs, sq_value rhs) {
 	}
 }
 
+// Bitwise operators only work on numerals, so anything else must be an imitation which overloads them.
+static sq_value bitwise_overload(sq_value lhs, sq_value rhs, const char *operator) {
+	struct sq_journey *change;
+	sq_value args[2] = { lhs, rhs };
+
+	if (sq_value_is_imitation(lhs) && (change = sq_imitation_lookup_change(AS_IMITATION(lhs), operator)))
+		return sq_journey_run_deprecated(change, 2, args);
+
+	die("cannot use '%s' on '%s' and '%s'", operator, TYPENAME(lhs), TYPENAME(rhs));
+}
+
+sq_value sq_value_band(sq_value lhs, sq_value rhs) {
+	if (!sq_value_is_numeral(lhs) || !sq_value_is_numeral(rhs))
+		return bitwise_overload(lhs, rhs, "&");
+
+	return sq_value_new(AS_NUMBER(lhs) & AS_NUMBER(rhs));
+}
+
+sq_value sq_value_bor(sq_value lhs, sq_value rhs) {
+	if (!sq_value_is_numeral(lhs) || !sq_value_is_numeral(rhs))
+		return bitwise_overload(lhs, rhs, "|");
+
+	return sq_value_new(AS_NUMBER(lhs) | AS_NUMBER(rhs));
+}
+
+sq_value sq_value_bxor(sq_value lhs, sq_value rhs) {
+	if (!sq_value_is_numeral(lhs) || !sq_value_is_numeral(rhs))
+		return bitwise_overload(lhs, rhs, "~");
+
+	return sq_value_new(AS_NUMBER(lhs) ^ AS_NUMBER(rhs));
+}
+
+sq_value sq_value_shl(sq_value lhs, sq_value rhs) {
+	if (!sq_value_is_numeral(lhs) || !sq_value_is_numeral(rhs))
+		return bitwise_overload(lhs, rhs, "<<");
+
+	sq_numeral number = AS_NUMBER(lhs), amount = AS_NUMBER(rhs);
+	if (amount < 0) die("cannot shift by a negative amount");
+
+	// anything other than `N` shifted this far will always overflow.
+	if (63 <= amount)
+		return new_checked_numeral(number != 0, 0, "shifting");
+
+	// shift as unsigned, as shifting negative numbers left is undefined in C.
+	sq_numeral result = (sq_numeral) ((uint64_t) number << amount);
+	return new_checked_numeral((result >> amount) != number, result, "shifting");
+}
+
+sq_value sq_value_shr(sq_value lhs, sq_value rhs) {
+	if (!sq_value_is_numeral(lhs) || !sq_value_is_numeral(rhs))
+		return bitwise_overload(lhs, rhs, ">>");
+
+	sq_numeral number = AS_NUMBER(lhs), amount = AS_NUMBER(rhs);
+	if (amount < 0) die("cannot shift by a negative amount");
+
+	// right shifts are arithmetic, so negative numbers stay negative.
+	return sq_value_new(number >> (63 < amount ? 63 : amount));
+}
+
 
 sq_value sq_value_call(sq_value tocall, struct sq_args args) {
 	assert(tocall != SQ_UNDEFINED);
@@ -611,9 +871,6 @@ sq_value sq_value_call(sq_value tocall, struct sq_args args) {
 	case SQ_G_JOURNEY:
 		return sq_journey_run(sq_value_as_journey(tocall), args);
 
-	case SQ_G_IMITATION:
-		todo("call imitation"); 
-
 	case SQ_G_OTHER:
 		if (sq_value_is_other(tocall)) {
 			sq_value result = sq_other_call(AS_OTHER(tocall), args);
@@ -625,7 +882,7 @@ sq_value sq_value_call(sq_value tocall, struct sq_args args) {
 		// else fallthrough
 
 	default:
-		sq_throw("cannot call '%s'.", TYPENAME(tocall));
+		sq_throw("cannot call '%s': it is not a journey or form", TYPENAME(tocall));
 	}
 }
 
@@ -646,6 +903,9 @@ struct sq_text *sq_value_to_text(sq_value value) {
 	case SQ_G_NUMERAL:
 		return sq_numeral_to_text(AS_NUMBER(value));
 
+	case SQ_G_DECIMAL:
+		return sq_decimal_to_text(AS_DECIMAL(value));
+
 	case SQ_G_TEXT:
 		sq_text_clone(AS_TEXT(value));
 		return AS_TEXT(value);
@@ -668,7 +928,12 @@ struct sq_text *sq_value_to_text(sq_value value) {
 				die("to_text for an imitation of '%s' didn't return a text", AS_IMITATION(value)->form->name);
 			return AS_TEXT(text);
 		}
-		// else fallthrough
+
+		// without a `to_text`, just say what the imitation is of.
+		const char *name = AS_IMITATION(value)->form->name;
+		char *text = xmalloc(strlen(name) + sizeof("<imitation of >"));
+		sprintf(text, "<imitation of %s>", name);
+		return sq_text_new(text);
 	}
 
 	case SQ_G_JOURNEY:
@@ -679,6 +944,45 @@ struct sq_text *sq_value_to_text(sq_value value) {
 	}
 }
 
+// Texts must be a single roman or arabic numeral, optionally surrounded by whitespace. Anything
+// else is an error, rather than silently becoming zero (or whatever prefix happened to parse).
+static sq_numeral text_to_numeral(const struct sq_text *text) {
+	const char *start = text->ptr, *end = NULL;
+	sq_numeral numeral;
+	bool negate = false;
+
+	while (isspace(*start))
+		++start;
+
+	if (*start == '-' && sq_numeral_starts(start + 1)) {
+		negate = true;
+		++start;
+	}
+
+	if (sq_numeral_starts(start)) {
+		numeral = sq_roman_to_numeral(start, &end);
+		if (negate) numeral = -numeral;
+	} else {
+		errno = 0;
+		numeral = strtoll(start, (char **) &end, 10);
+
+		if (end == start)
+			end = NULL;
+		else if (errno == ERANGE || numeral < NUMERAL_MIN || NUMERAL_MAX < numeral)
+			sq_throw("numeral overflow when converting '%s' to a numeral", text->ptr);
+	}
+
+	if (end != NULL) {
+		while (isspace(*end))
+			++end;
+
+		if (*end == '\0')
+			return numeral;
+	}
+
+	sq_throw("cannot convert '%s' to a numeral", text->ptr);
+}
+
 sq_numeral sq_value_to_numeral(sq_value value) {
 	switch (SQ_VTAG(value)) {
 	case SQ_G_OTHER:
@@ -690,11 +994,18 @@ sq_numeral sq_value_to_numeral(sq_value value) {
 	case SQ_G_NUMERAL:
 		return AS_NUMBER(value);
 
+	// decimals are truncated towards zero, like numeral division.
+	case SQ_G_DECIMAL: {
+		sq_decimal decimal = AS_DECIMAL(value), limit = -(sq_decimal) (INT64_MIN >> SQ_VSHIFT);
+
+		if (!(-limit <= decimal && decimal < limit))
+			sq_throw("decimal is out of range for a numeral");
+
+		return (sq_numeral) decimal;
+	}
+
 	case SQ_G_TEXT:
-		if (sq_numeral_starts(AS_STR(value)))
-			return sq_roman_to_numeral(AS_STR(value), NULL);
-		else
-			return strtoll(AS_STR(value), NULL, 10);
+		return text_to_numeral(AS_TEXT(value));
 
 	case SQ_G_BOOK:
 		return sq_value_new(sq_book_to_text(AS_BOOK(value)));
@@ -721,6 +1032,21 @@ sq_numeral sq_value_to_numeral(sq_value value) {
 	}
 }
 
+sq_decimal sq_value_to_decimal(sq_value value) {
+	switch (SQ_VTAG(value)) {
+	case SQ_G_DECIMAL:
+		return AS_DECIMAL(value);
+
+	case SQ_G_TEXT:
+		if (!sq_numeral_starts(AS_STR(value)))
+			return strtod(AS_STR(value), NULL);
+		// else fallthrough
+
+	default:
+		return (sq_decimal) sq_value_to_numeral(value);
+	}
+}
+
 bool sq_value_to_veracity(sq_value value) {
 	switch (SQ_VTAG(value)) {
 	case SQ_G_OTHER:
@@ -732,6 +1058,9 @@ bool sq_value_to_veracity(sq_value value) {
 	case SQ_G_NUMERAL:
 		return AS_NUMBER(value);
 
+	case SQ_G_DECIMAL:
+		return AS_DECIMAL(value) != 0;
+
 	case SQ_G_TEXT:
 		return *AS_STR(value);
 
@@ -750,7 +1079,8 @@ bool sq_value_to_veracity(sq_value value) {
 				die("to_veracity for an imitation of '%s' didn't return a veracity", AS_IMITATION(value)->form->name);
 			return sq_value_as_veracity(veracity);
 		}
-		// else fallthrough
+
+		return true;
 	}
 
 	case SQ_G_FORM:
@@ -771,7 +1101,7 @@ size_t sq_value_length(sq_value value) {
 		return sq_value_as_codex(value)->length;
 
 	case SQ_G_TEXT:
-		return AS_TEXT(value)->length;
+		return sq_text_nchars(AS_TEXT(value));
 
 	case SQ_G_IMITATION: {
 		struct sq_journey *length = sq_imitation_lookup_change(AS_IMITATION(value), "length");
@@ -786,7 +1116,12 @@ size_t sq_value_length(sq_value value) {
 	}
 
 	case SQ_G_OTHER:
+		if (sq_value_is_other(value) && AS_OTHER(value)->kind == SQ_OK_RANGE)
+			return sq_range_length(sq_other_as_range(AS_OTHER(value)));
+		// else fallthrough
+
 	case SQ_G_NUMERAL:
+	case SQ_G_DECIMAL:
 	case SQ_G_FORM:
 	case SQ_G_JOURNEY:
 		die("cannot get length of %s", TYPENAME(value));
@@ -806,11 +1141,17 @@ struct sq_book *sq_value_to_book(sq_value value) {
 		const struct sq_text *text = AS_TEXT(value);
 		struct sq_book *book = sq_book_allocate(text->length);
 
-		for (unsigned i = 0; i < text->length; ++i) {
-			char *data = xmalloc(2);
-			data[0] = text->ptr[i];
-			data[1] = '\0';
-			book->pages[book->length++] = sq_value_new(sq_text_new2(data, 1));
+		for (unsigned i = 0, len; i < text->length; i += len) {
+			// split on utf-8 characters, not bytes.
+			unsigned char c = text->ptr[i];
+			len = c < 0x80 ? 1 : (c & 0xe0) == 0xc0 ? 2 : (c & 0xf0) == 0xe0 ? 3 : 4;
+
+			if (text->length < i + len)
+				len = text->length - i;
+
+			char *data = memdup(text->ptr + i, len + 1);
+			data[len] = '\0';
+			book->pages[book->length++] = sq_value_new(sq_text_new2(data, len));
 		}
 
 		return book;
@@ -871,6 +1212,7 @@ sq_value sq_value_get_attr(sq_value soul, const char *attr) {
 		break;
 
 	case SQ_G_NUMERAL:
+	case SQ_G_DECIMAL:
 	case SQ_G_CODEX:
 		break;
 	}
@@ -914,6 +1256,7 @@ void sq_value_set_attr(sq_value soul, const char *attr, sq_value value) {
 		// else, fallthrough
 
 	case SQ_G_NUMERAL:
+	case SQ_G_DECIMAL:
 	case SQ_G_TEXT:
 	case SQ_G_JOURNEY:
 	case SQ_G_CODEX:
@@ -943,6 +1286,7 @@ bool sq_value_matches(sq_value formlike, sq_value to_check) {
 	case SQ_G_TEXT:
 		// temporary hack until we get forms for primitives too
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Numeral") && sq_value_is_numeral(to_check)) return true;
+		if (!strcmp(sq_value_as_text(formlike)->ptr, "Decimal") && sq_value_is_decimal(to_check)) return true;
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Text") && sq_value_is_text(to_check)) return true;
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Veracity") && sq_value_is_veracity(to_check)) return true;
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Ni") && to_check == SQ_NI) return true;
@@ -951,6 +1295,8 @@ bool sq_value_matches(sq_value formlike, sq_value to_check) {
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Journey") && sq_value_is_journey(to_check)) return true;
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Book") && sq_value_is_book(to_check)) return true;
 		if (!strcmp(sq_value_as_text(formlike)->ptr, "Codex") && sq_value_is_codex(to_check)) return true;
+		if (!strcmp(sq_value_as_text(formlike)->ptr, "Range") && sq_value_is_other(to_check)
+			&& AS_OTHER(to_check)->kind == SQ_OK_RANGE) return true;
 		// fallthrough
 
 	case SQ_G_OTHER:
@@ -958,6 +1304,7 @@ bool sq_value_matches(sq_value formlike, sq_value to_check) {
 			return sq_other_matches(AS_OTHER(formlike), to_check);
 		// else, fallthrough
 	case SQ_G_NUMERAL:
+	case SQ_G_DECIMAL:
 		return sq_value_eql(formlike, to_check);
 
 	case SQ_G_BOOK: